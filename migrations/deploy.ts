@@ -9,4 +9,10 @@ module.exports = async function (provider) {
   anchor.setProvider(provider);
 
   // Add your deploy script here.
+  //
+  // Upgrading an existing deployment in place is not supported. Config, Epoch and UserStake
+  // gained fields, and the StakeEntry elements inside UserStake.stake_entries grew, so accounts
+  // written by an older build cannot be deserialized and a realloc alone cannot fix them.
+  // Deploy under a fresh program id, run `initialize`, and have stakers withdraw from the old
+  // program and stake again in the new one.
 };
@@ -6,4 +6,15 @@ pub const VAULT: &str = "vault";
 pub const USER: &str = "user";
 pub const REWARD: &str = "reward";
 pub const EPOCH: &str = "epoch";
-pub const STAKE: &str = "stake";
\ No newline at end of file
+pub const STAKE: &str = "stake";
+pub const RECEIPT: &str = "receipt";
+pub const LIQUID: &str = "liquid";
+pub const VOTER_WEIGHT_RECORD: &str = "voter-weight-record";
+pub const MAX_VOTER_WEIGHT_RECORD: &str = "max-voter-weight-record";
+pub const SCHEDULE: &str = "schedule";
+pub const MERKLE_CLAIM: &str = "merkle_claim";
+pub const REFERRAL: &str = "referral";
+pub const BOOST_VAULT: &str = "boost_vault";
+pub const NFT_ESCROW: &str = "nft_escrow";
+pub const NFT_BOOST_LOCK: &str = "nft_boost_lock";
+pub const UNWRAP: &str = "unwrap";
\ No newline at end of file
@@ -28,4 +28,110 @@ pub enum RichieError {
     InvalidLockPeriod,
     #[msg("No stake available for withdrawal.")]
     NothingToWithdraw,
-}
\ No newline at end of file
+    #[msg("Stake entry not found.")]
+    EntryNotFound,
+    #[msg("New lock must end later than the current one.")]
+    InvalidLockExtension,
+    #[msg("Stake entries cannot be merged.")]
+    InvalidMerge,
+    #[msg("Invalid recipient.")]
+    InvalidRecipient,
+    #[msg("Too many stake entries.")]
+    TooManyEntries,
+    #[msg("Stake entry is controlled by its receipt NFT.")]
+    ReceiptControlled,
+    #[msg("Signer does not hold the stake receipt.")]
+    InvalidReceipt,
+    #[msg("Liquid staking is not initialized.")]
+    LiquidNotInitialized,
+    #[msg("Rewards can only be compounded when the reward mint is the stake mint.")]
+    CannotCompound,
+    #[msg("Invalid operator permissions.")]
+    InvalidPermissions,
+    #[msg("Withdrawals must go through request_unstake.")]
+    UnbondingRequired,
+    #[msg("Stake entry is unbonding.")]
+    EntryUnbonding,
+    #[msg("Stake entry is not unbonding.")]
+    EntryNotUnbonding,
+    #[msg("Unbonding period has not passed yet.")]
+    UnbondingNotFinished,
+    #[msg("Invalid unbonding period.")]
+    InvalidUnbondingPeriod,
+    #[msg("Claim the receipt rewards first.")]
+    ReceiptRewardPending,
+    #[msg("Governance realm is not configured.")]
+    GovernanceNotConfigured,
+    #[msg("Emission schedule is full.")]
+    ScheduleFull,
+    #[msg("No funded epoch left in the emission schedule.")]
+    ScheduleEmpty,
+    #[msg("Invalid emission policy.")]
+    InvalidEmissionPolicy,
+    #[msg("Too many reward funders provided.")]
+    TooManyFunders,
+    #[msg("Epoch rewards can no longer be changed.")]
+    EpochRewardLocked,
+    #[msg("Claim expiry is not enabled.")]
+    ClaimExpiryDisabled,
+    #[msg("No expired rewards to sweep.")]
+    NothingToSweep,
+    #[msg("Invalid treasury account.")]
+    InvalidTreasury,
+    #[msg("Epoch rewards were already settled or published.")]
+    EpochAlreadySettled,
+    #[msg("Epoch rewards are not distributed by merkle proof.")]
+    NotMerkleEpoch,
+    #[msg("Invalid merkle proof.")]
+    InvalidMerkleProof,
+    #[msg("Merkle payouts exceed the epoch reward.")]
+    MerkleTotalExceeded,
+    #[msg("Reward claim has expired.")]
+    ClaimExpired,
+    #[msg("Too many reward mints.")]
+    TooManyRewardMints,
+    #[msg("Reward mint is not registered for this pool.")]
+    UnknownRewardMint,
+    #[msg("Missing or invalid reward accounts.")]
+    InvalidRewardAccounts,
+    #[msg("Reward mint is already registered.")]
+    RewardMintRegistered,
+    #[msg("Fee exceeds the maximum.")]
+    InvalidFee,
+    #[msg("Amount exceeds the withdrawable balance.")]
+    InsufficientFunds,
+    #[msg("Invalid referrer.")]
+    InvalidReferrer,
+    #[msg("Referral accounts are missing.")]
+    ReferralRequired,
+    #[msg("Loyalty tiers must be ascending, within the limit and under the bonus cap.")]
+    InvalidLoyaltyTiers,
+    #[msg("NFT does not qualify for the boost.")]
+    InvalidNftBoost,
+    #[msg("Too many allowlisted NFT keys.")]
+    TooManyNftKeys,
+    #[msg("Boost token is not configured.")]
+    BoostNotConfigured,
+    #[msg("Instruction does not match the pool type.")]
+    InvalidPoolType,
+    #[msg("Staked NFTs are withdrawn with withdraw_nft.")]
+    NftEntry,
+    #[msg("NFT is not part of the pool collection.")]
+    InvalidNft,
+    #[msg("Pool mint is not the native mint.")]
+    NotNativeMint,
+    #[msg("Amount is below the minimum stake.")]
+    BelowMinimumStake,
+    #[msg("Position was used to vote and cannot be transferred yet.")]
+    VoteLocked,
+    #[msg("Vote lock cannot be negative.")]
+    InvalidVoteLock,
+    #[msg("Merkle claims for this epoch have not expired yet.")]
+    ClaimNotExpired,
+    #[msg("Early NFT exits need a reward token account for the penalty.")]
+    PenaltyAccountRequired,
+    #[msg("The NFT penalty can only be raised while nothing is staked.")]
+    InvalidNftPenalty,
+    #[msg("The staker list is full.")]
+    TooManyStakers,
+}
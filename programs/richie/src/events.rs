@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EpochRewardAdded {
+    pub epoch_index: u64,
+    pub reward_mint: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub total_reward: u64,
+}
+
+#[event]
+pub struct ExpiredRewardsSwept {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey, // Pubkey::default() when returned to the pool
+}
+
+#[event]
+pub struct MerkleRootPublished {
+    pub epoch_index: u64,
+    pub merkle_root: [u8; 32],
+    pub total: u64,
+}
+
+#[event]
+pub struct UnclaimedMerkleReleased {
+    pub epoch_index: u64,
+    pub amount: u64,
+    pub treasury: Pubkey, // Pubkey::default() when returned to the pool
+}
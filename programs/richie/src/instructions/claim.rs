@@ -1,12 +1,11 @@
 use anchor_lang::prelude::*;
 
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{transfer, Token, Mint, TokenAccount, Transfer, burn, Burn}
-};
+use anchor_spl::token::{transfer, spl_token::native_mint, Token, Mint, TokenAccount, Transfer, burn, Burn};
 
 use crate::{ constants::*, error::RichieError, state::* };
 
+use super::{ dual_boost::reapply_dual_boost, stake::record_stake };
+
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(mut)]
@@ -110,10 +109,40 @@ pub struct AdminRewardTokenWithdraw<'info> {
 }
 
 
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = config.treasury @ RichieError::InvalidTreasury
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct Claim<'info> {
     #[account(mut)]
-    pub user : Signer<'info>,
+    pub authority : Signer<'info>, // owner or operator
+
+    /// CHECK: position owner, bound to user_stake by its seeds
+    pub user: AccountInfo<'info>,
    
     #[account(
         mut,
@@ -136,99 +165,301 @@ pub struct Claim<'info> {
     )]
     pub reward_vault: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(address = config.reward_token_mint)]
     pub reward_mint: Account<'info, Mint>,
 
     #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = reward_mint,
-        associated_token::authority = user
+        mut,
+        token::mint = reward_mint
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub destination: Account<'info, TokenAccount>, // any reward-mint account; the owner's when an operator signs
 
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
-pub fn claim(ctx: Context<Claim>) -> Result<()> {
-    let user_stake = &mut ctx.accounts.user_stake;
-    let amount = user_stake.pending_reward;
-    require!(amount > 0, RichieError::NoReward);
+#[derive(Accounts)]
+pub struct WithdrawAndClaim<'info> {
+    #[account(mut)]
+    pub user : Signer<'info>,
+   
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(mut)]
+    pub stake_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = stake_token_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub to_token_account: Box<Account<'info, TokenAccount>>, // user's $RICHIE
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = config.reward_token_mint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct Compound<'info> {
+    pub authority: Signer<'info>, // owner or operator
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
 
-    user_stake.pending_reward = 0;
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the rewards selected by `reward_mask`: bit 0 is the primary reward, bit `slot + 1` the
+/// extra reward in `config.extra_reward_mints[slot]`. Each selected extra reward takes two
+/// remaining accounts, its [REWARD, mint] vault followed by the destination token account.
+pub fn claim<'info>(ctx: Context<'_, '_, '_, 'info, Claim<'info>>, reward_mask: u8) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    require!(
+        user_stake.is_authorized(ctx.accounts.authority.key(), UserStake::OPERATOR_CLAIM),
+        RichieError::UnAuthorized
+    );
+    let authority = ctx.accounts.authority.key();
 
     let (_, bump) = Pubkey::find_program_address(&[CONFIG.as_bytes()], ctx.program_id);
     let vault_seeds = &[CONFIG.as_bytes(), &[bump]];
     let signer = &[&vault_seeds[..]];
 
+    let mut paid = false;
+
+    if reward_mask & Config::PRIMARY_REWARD != 0 && user_stake.pending_reward > 0 {
+        user_stake.check_recipient(authority, ctx.accounts.destination.owner)?;
+        let amount = user_stake.take_pending_reward();
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: config.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, amount)?;
+        paid = true;
+    }
+
+    // Operators may only claim to the owner, see UserStake::check_recipient
+    let required_owner = (authority != user_stake.owner).then_some(user_stake.owner);
+    paid |= pay_extra_rewards(
+        &ctx.accounts.token_program,
+        config,
+        bump,
+        ctx.remaining_accounts,
+        &mut user_stake.extra_pending_rewards,
+        reward_mask,
+        required_owner,
+    )?;
+
+    require!(paid, RichieError::NoReward);
+
+    Ok(())
+}
+
+/// Pays every extra reward selected by `reward_mask` out of `pending`, taking its [REWARD, mint]
+/// vault and destination token account, in that order, from `remaining_accounts`.
+/// With `required_owner` every destination must belong to it. Returns whether anything was paid.
+pub(crate) fn pay_extra_rewards<'info>(
+    token_program: &Program<'info, Token>,
+    config: &Account<'info, Config>,
+    config_bump: u8,
+    remaining_accounts: &[AccountInfo<'info>],
+    pending: &mut [u64; Config::MAX_EXTRA_REWARDS],
+    reward_mask: u8,
+    required_owner: Option<Pubkey>,
+) -> Result<bool> {
+    let vault_seeds = &[CONFIG.as_bytes(), &[config_bump]];
+    let signer = &[&vault_seeds[..]];
+
+    let mut paid = false;
+    let mut remaining_accounts = remaining_accounts.iter();
+    for (slot, reward_mint) in config.extra_reward_mints.iter().enumerate() {
+        if reward_mask & Config::extra_reward_bit(slot) == 0 {
+            continue;
+        }
+        let extra_reward_vault = remaining_accounts.next().ok_or(RichieError::InvalidRewardAccounts)?;
+        let destination = remaining_accounts.next().ok_or(RichieError::InvalidRewardAccounts)?;
+
+        let (vault_key, _) = Pubkey::find_program_address(&[REWARD.as_bytes(), reward_mint.as_ref()], &crate::ID);
+        require_keys_eq!(extra_reward_vault.key(), vault_key, RichieError::InvalidRewardAccounts);
+
+        let amount = std::mem::take(&mut pending[slot]);
+        if amount == 0 {
+            continue;
+        }
+        let destination_account = TokenAccount::try_deserialize(&mut &destination.data.borrow()[..])?;
+        if let Some(owner) = required_owner {
+            require_keys_eq!(destination_account.owner, owner, RichieError::InvalidRecipient);
+        }
+
+        // The token program rejects a destination of another mint
+        let cpi_accounts = Transfer {
+            from: extra_reward_vault.clone(),
+            to: destination.clone(),
+            authority: config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, amount)?;
+        paid = true;
+    }
+
+    Ok(paid)
+}
+
+pub fn compound(ctx: Context<Compound>, index: u64, lock_period: u8) -> Result<()> {
+    let owner = ctx.accounts.user_stake.owner;
+    require!(
+        ctx.accounts.user_stake.is_authorized(ctx.accounts.authority.key(), UserStake::OPERATOR_COMPOUND),
+        RichieError::UnAuthorized
+    );
+    // Locking beyond the lowest tier is a lock decision, which operators need OPERATOR_EXTEND_LOCK for
+    if lock_period > 1 {
+        require!(
+            ctx.accounts.user_stake.is_authorized(ctx.accounts.authority.key(), UserStake::OPERATOR_EXTEND_LOCK),
+            RichieError::UnAuthorized
+        );
+    }
+    require!(
+        ctx.accounts.config.reward_token_mint == ctx.accounts.config.stake_token_mint,
+        RichieError::CannotCompound
+    );
+
+    require!(ctx.accounts.user_stake.pending_reward > 0, RichieError::NoReward);
+    let amount = ctx.accounts.user_stake.take_pending_reward();
+
+    // Rewards are restaked as a new entry of the owner
+    let entry_id = record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.user_stake,
+        owner,
+        index,
+        amount,
+        lock_period,
+        Pubkey::default(),
+        Pubkey::default(),
+        ctx.accounts.boost_nft_account.as_ref(),
+    )?;
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
     let cpi_accounts = Transfer {
         from: ctx.accounts.reward_vault.to_account_info(),
-        to: ctx.accounts.user_reward_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
         authority: ctx.accounts.config.to_account_info(),
     };
-
     let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
     transfer(cpi_ctx, amount)?;
 
+    msg!("🔁 Compounded {} reward into entry {}", amount, entry_id);
+
     Ok(())
 }
 
-pub fn withdraw(ctx: Context<Withdraw>, index: u64) -> Result<()> {
+pub fn withdraw(ctx: Context<Withdraw>, entry_id: u64) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let user_stake = &mut ctx.accounts.user_stake;
-    let stake_vault = &ctx.accounts.stake_vault;
-    let to_token_account = &ctx.accounts.to_token_account;
     let epoch = &mut ctx.accounts.epoch;
 
-    let clock = Clock::get()?;
-
     let current_index = config.index;
-    let mut total_withdraw: u64 = 0;
-    let mut total_penalty: u64 = 0;
 
     msg!("🔍 Starting withdrawal for user: {}", ctx.accounts.user.key());
     msg!("📆 Current epoch index: {}", current_index);
-    msg!("🎯 Target epoch index to withdraw from: {}", index);
+    msg!("🎯 Target stake entry id to withdraw: {}", entry_id);
     msg!("🧾 Stake entries before withdrawal: {}", user_stake.stake_entries.len());
 
-    user_stake.stake_entries.retain(|entry| {
-        if entry.last_staked_epoch_index == index {
-            let end_epoch = entry.last_staked_epoch_index + entry.lock_period as u64;
-            let mut unearned_curve = 0;
-
-            if current_index < end_epoch {
-                let penalty = entry.amount * 5 / 100;
-                total_penalty += penalty;
-                total_withdraw += entry.amount - penalty;
-
-                unearned_curve = entry.boosted_curve;
-
-                msg!(
-                    "⚠️ Early withdrawal: lock ends at epoch {}, applying 5% penalty ({} lamports)",
-                    end_epoch,
-                    penalty
-                );
-            } else {
-                total_withdraw += entry.amount;
-                unearned_curve = entry.base_curve;
-
-                msg!(
-                    "✅ On-time withdrawal: lock ended at epoch {}, no penalty",
-                    end_epoch
-                );
-            }
-            config.total_staked -= entry.amount;
-
-            epoch.total_curve = epoch.total_curve.saturating_sub(unearned_curve);
-            msg!("📉 Subtracted unearned curve: {}", unearned_curve);
-
-            false // remove this entry
-        } else {
-            true // keep
-        }
-    });
+    require!(config.unbonding_period == 0, RichieError::UnbondingRequired);
+    let entry = user_stake.remove_entry(entry_id)?;
+    require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+    require!(!entry.is_nft(), RichieError::NftEntry);
+    let (total_withdraw, total_penalty) = release_entry(config, epoch, user_stake, &entry)?;
 
     msg!("💰 Total withdrawable amount: {}", total_withdraw);
     msg!("🧾 Total penalty collected: {}", total_penalty);
@@ -237,42 +468,175 @@ pub fn withdraw(ctx: Context<Withdraw>, index: u64) -> Result<()> {
 
     require!(total_withdraw > 0, RichieError::NothingToWithdraw);
 
-    // Transfer tokens from vault to user's token account
+    // Transfer tokens from vault to user's token account and burn the penalty
+    pay_out_principal(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.config,
+        ctx.bumps.config,
+        &ctx.accounts.stake_token_mint,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.to_token_account,
+        total_withdraw,
+        total_penalty,
+    )?;
+
+    msg!("✅ Successfully transferred {} lamports to user.", total_withdraw);
+
+    Ok(())
+}
+
+/// Withdraws `entry_id` and pays the rewards selected by `reward_mask`, laid out as in `claim`.
+pub fn withdraw_and_claim<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawAndClaim<'info>>, entry_id: u64, reward_mask: u8) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(config.unbonding_period == 0, RichieError::UnbondingRequired);
+    let entry = user_stake.remove_entry(entry_id)?;
+    require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+    require!(!entry.is_nft(), RichieError::NftEntry);
+    let (total_withdraw, total_penalty) = release_entry(config, epoch, user_stake, &entry)?;
+    require!(total_withdraw > 0, RichieError::NothingToWithdraw);
+
+    // Only the owner signs here, so rewards may go to any account they pick
+    let reward = if reward_mask & Config::PRIMARY_REWARD != 0 { user_stake.take_pending_reward() } else { 0 };
+
     let bump = ctx.bumps.config;
     let seeds = &[CONFIG.as_bytes(), &[bump]];
     let signer = &[&seeds[..]];
 
+    pay_out_principal(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.config,
+        bump,
+        &ctx.accounts.stake_token_mint,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.to_token_account,
+        total_withdraw,
+        total_penalty,
+    )?;
+
+    // Nothing pending is fine here; the exit alone is still worth doing
+    if reward > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, reward)?;
+    }
+
+    pay_extra_rewards(
+        &ctx.accounts.token_program,
+        &ctx.accounts.config,
+        bump,
+        ctx.remaining_accounts,
+        &mut ctx.accounts.user_stake.extra_pending_rewards,
+        reward_mask,
+        None,
+    )?;
+
+    msg!("✅ Withdrew {} and claimed {} reward for entry {}", total_withdraw, reward, entry_id);
+
+    Ok(())
+}
+
+/// Sends `amount` of released principal from stake_vault to `destination` and burns the early-exit `penalty`.
+/// Wrapped SOL cannot be burned, so native pools keep the penalty in stake_vault for withdraw_native_penalties.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_out_principal<'info>(
+    token_program: &Program<'info, Token>,
+    config: &mut Account<'info, Config>,
+    config_bump: u8,
+    stake_token_mint: &Account<'info, Mint>,
+    stake_vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    amount: u64,
+    penalty: u64,
+) -> Result<()> {
+    let seeds = &[CONFIG.as_bytes(), &[config_bump]];
+    let signer = &[&seeds[..]];
+
     let cpi_accounts = Transfer {
         from: stake_vault.to_account_info(),
-        to: to_token_account.to_account_info(),
-        authority: ctx.accounts.config.to_account_info(),
+        to: destination.to_account_info(),
+        authority: config.to_account_info(),
     };
-    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
-    anchor_spl::token::transfer(cpi_ctx, total_withdraw)?;
-
-    msg!("✅ Successfully transferred {} lamports to user.", total_withdraw);
-
-    // Burn the penalty tokens
-    if total_penalty > 0 {
-        msg!("🔥 Burning {} penalty tokens from vault...", total_penalty);
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, amount)?;
 
+    if penalty > 0 && stake_token_mint.key() == native_mint::ID {
+        config.native_penalties += penalty;
+    } else if penalty > 0 {
         let burn_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.to_account_info(),
             Burn {
-                mint: ctx.accounts.stake_token_mint.to_account_info(),
+                mint: stake_token_mint.to_account_info(),
                 from: stake_vault.to_account_info(),
-                authority: ctx.accounts.config.to_account_info(),
+                authority: config.to_account_info(),
             },
             signer,
         );
-
-        burn(burn_ctx, total_penalty)?;
-        msg!("🔥 Burned penalty tokens successfully.");
+        burn(burn_ctx, penalty)?;
     }
 
     Ok(())
 }
 
+/// Books the removal of `entry` from the pool and returns the amount to pay out and the penalty to burn.
+/// Leaving before the lock ends also costs the position its loyalty streak.
+pub(crate) fn release_entry(config: &mut Config, epoch: &mut Epoch, user_stake: &mut UserStake, entry: &StakeEntry) -> Result<(u64, u64)> {
+    // Unbonding entries were already released at request_unstake
+    require!(!entry.is_unbonding(), RichieError::EntryUnbonding);
+
+    // After merkle epochs the running epoch's total counts the entry at its caught-up curve
+    let mut entry = entry.clone();
+    entry.catch_up(config.index, epoch.stake_duration, user_stake.applied_bonus(config));
+
+    let end_epoch = entry.lock_end();
+    let mut penalty = 0;
+    let unearned_curve;
+
+    if config.index < end_epoch {
+        penalty = entry.amount * 5 / 100;
+        unearned_curve = entry.boosted_curve;
+        // A receipt holder exiting early does not cost the owner their streak
+        if !entry.has_receipt() {
+            user_stake.loyalty_streak = 0;
+        }
+
+        msg!(
+            "⚠️ Early withdrawal: lock ends at epoch {}, applying 5% penalty ({} lamports)",
+            end_epoch,
+            penalty
+        );
+    } else {
+        unearned_curve = entry.base_curve;
+
+        msg!(
+            "✅ On-time withdrawal: lock ended at epoch {}, no penalty",
+            end_epoch
+        );
+    }
+    config.total_staked -= entry.amount;
+    config.remove_voting_lock(entry.amount, end_epoch);
+
+    if entry.calculated_index == config.index {
+        // Already settled: this epoch's share is paid, and the whole next-epoch curve is in config.total_curve
+        config.total_curve = config.total_curve.saturating_sub(entry.boosted_curve);
+        msg!("📉 Subtracted next epoch curve: {}", entry.boosted_curve);
+    } else {
+        epoch.total_curve = epoch.total_curve.saturating_sub(unearned_curve);
+        msg!("📉 Subtracted unearned curve: {}", unearned_curve);
+    }
+
+    // Less principal raises the boost/main ratio; request_unstake re-applies once the entry is marked unbonding
+    reapply_dual_boost(config, epoch, user_stake)?;
+
+    Ok((entry.amount - penalty, penalty))
+}
+
 
 
 
@@ -308,6 +672,11 @@ pub fn admin_withdraw_reward_mint(ctx: Context<AdminRewardTokenWithdraw>, amount
     let to_token_account = &ctx.accounts.to_token_account;
 
     require!(config.admin ==  ctx.accounts.owner.key(), RichieError::UnAuthorized);
+    // Accrued protocol fees are only paid out through withdraw_protocol_fees
+    require!(
+        amount <= reward_vault.amount.saturating_sub(config.accrued_fees),
+        RichieError::InsufficientFunds
+    );
 
     // Transfer tokens from vault to user's token account
     let bump = ctx.bumps.config;
@@ -327,3 +696,28 @@ pub fn admin_withdraw_reward_mint(ctx: Context<AdminRewardTokenWithdraw>, amount
     Ok(())
 }
 
+
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        config.treasurer != Pubkey::default() && config.treasurer == ctx.accounts.treasurer.key(),
+        RichieError::UnAuthorized
+    );
+
+    let amount = std::mem::take(&mut config.accrued_fees);
+    require!(amount > 0, RichieError::NothingToWithdraw);
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, amount)?;
+
+    Ok(())
+}
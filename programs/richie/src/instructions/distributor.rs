@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{ constants::*, error::RichieError, events::{MerkleRootPublished, UnclaimedMerkleReleased}, merkle, state::* };
+
+use super::expiry::route_expired_rewards;
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct PublishMerkleRoot<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ClaimMerkleReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // anyone may submit a proof, the reward is credited to the owner
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [MERKLE_CLAIM.as_bytes(), &index.to_le_bytes(), user_stake.owner.as_ref()],
+        bump,
+        space = 8 + MerkleClaim::LEN
+    )]
+    pub merkle_claim: Box<Account<'info, MerkleClaim>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ReleaseUnclaimedMerkle<'info> {
+    pub cranker: Signer<'info>, // anyone may release
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury: Option<Box<Account<'info, TokenAccount>>>, // required when config.expired_reward_treasury is set
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Publishes off-chain computed payouts for a finished epoch instead of settling it through
+/// manage_staker_reward. `total` is reserved for claims and the rest rolls into the next epoch;
+/// whatever of `total` goes unclaimed is freed by release_unclaimed_merkle once claims expire.
+/// No position is settled for a merkle epoch, so `next_total_curve`, from merkle::rolled_total_curve,
+/// stands in for the curves settlement would have added up for the next epoch.
+pub fn publish_merkle_root(
+    ctx: Context<PublishMerkleRoot>,
+    index: u64,
+    merkle_root: [u8; 32],
+    total: u64,
+    next_total_curve: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(index > 0 && index == config.index, RichieError::InvalidEpochIndex);
+    require!(epoch.staked_end_time < clock.unix_timestamp, RichieError::UnFinishedEpoch);
+    require!(!epoch.claimable && !epoch.is_merkle(), RichieError::EpochAlreadySettled);
+    require!(merkle_root != [0; 32], RichieError::InvalidMerkleProof);
+    require!(total <= epoch.reward, RichieError::MerkleTotalExceeded);
+
+    epoch.merkle_root = merkle_root;
+    epoch.allocated = total;
+    epoch.claimable = true;
+    config.total_curve = next_total_curve;
+
+    emit!(MerkleRootPublished {
+        epoch_index: index,
+        merkle_root,
+        total,
+    });
+
+    Ok(())
+}
+
+/// Credits a merkle payout to the owner's pending reward, to be claimed or compounded as usual.
+pub fn claim_merkle_reward(ctx: Context<ClaimMerkleReward>, index: u64, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(epoch.is_merkle(), RichieError::NotMerkleEpoch);
+    require!(
+        config.claim_expiry_epochs == 0 || index + config.claim_expiry_epochs >= config.index,
+        RichieError::ClaimExpired
+    );
+
+    let leaf = merkle::leaf(index, &user_stake.owner, amount);
+    require!(merkle::verify(&proof, &epoch.merkle_root, leaf), RichieError::InvalidMerkleProof);
+
+    // A bad root must not let claims drain more than was reserved for the epoch
+    epoch.merkle_claimed += amount;
+    require!(epoch.merkle_claimed <= epoch.allocated, RichieError::MerkleTotalExceeded);
+
+    user_stake.credit_reward(index, amount);
+    // Stands in for the streak settlement would have extended
+    user_stake.extend_streak(index);
+
+    let merkle_claim = &mut ctx.accounts.merkle_claim;
+    merkle_claim.epoch_index = index;
+    merkle_claim.owner = user_stake.owner;
+    merkle_claim.amount = amount;
+
+    Ok(())
+}
+
+/// Releases the part of a merkle epoch's reserved total that was not claimed before
+/// `claim_expiry_epochs` ran out, routed like swept expired rewards.
+pub fn release_unclaimed_merkle(ctx: Context<ReleaseUnclaimedMerkle>, index: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(epoch.is_merkle(), RichieError::NotMerkleEpoch);
+    require!(config.claim_expiry_epochs > 0, RichieError::ClaimExpiryDisabled);
+    // Mirrors claim_merkle_reward: once this holds no proof for the epoch can be claimed
+    require!(index + config.claim_expiry_epochs < config.index, RichieError::ClaimNotExpired);
+
+    let amount = epoch.allocated - epoch.merkle_claimed;
+    require!(amount > 0, RichieError::NothingToSweep);
+    epoch.allocated = epoch.merkle_claimed;
+
+    let treasury = route_expired_rewards(
+        config,
+        ctx.bumps.config,
+        &ctx.accounts.reward_vault,
+        ctx.accounts.treasury.as_deref(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(UnclaimedMerkleReleased {
+        epoch_index: index,
+        amount,
+        treasury,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+#[derive(Accounts)]
+pub struct InitializeBoostVault<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub boost_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [BOOST_VAULT.as_bytes()],
+        bump,
+        token::mint = boost_token_mint,
+        token::authority = config
+    )]
+    pub boost_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageBoostStake<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>, // boosts an existing main stake position
+
+    #[account(
+        mut,
+        token::mint = config.boost_token_mint,
+        token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [BOOST_VAULT.as_bytes()],
+        bump
+    )]
+    pub boost_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_boost_vault(ctx: Context<InitializeBoostVault>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(config.boost_token_mint == Pubkey::default(), RichieError::BoostNotConfigured);
+
+    config.boost_token_mint = ctx.accounts.boost_token_mint.key();
+    config.boost_vault = ctx.accounts.boost_vault.key();
+
+    Ok(())
+}
+
+pub fn stake_boost(ctx: Context<ManageBoostStake>, amount: u64) -> Result<()> {
+    require!(amount > 0, RichieError::InsufficientStake);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.boost_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    let user_stake = &mut ctx.accounts.user_stake;
+    user_stake.boost_staked += amount;
+    reapply_dual_boost(&mut ctx.accounts.config, &mut ctx.accounts.epoch, user_stake)?;
+
+    Ok(())
+}
+
+pub fn unstake_boost(ctx: Context<ManageBoostStake>, amount: u64) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    require!(amount > 0 && amount <= user_stake.boost_staked, RichieError::InsufficientStake);
+
+    user_stake.boost_staked -= amount;
+    reapply_dual_boost(&mut ctx.accounts.config, &mut ctx.accounts.epoch, user_stake)?;
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.boost_vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+/// Moves the dual-token bonus of the position's locked entries to the current boost/main ratio.
+/// Only the rest of the running epoch is re-weighted, so a boost held for part of the epoch
+/// earns for that part; settlement applies the new bonus to the whole next epoch. Entries
+/// already settled for the next epoch are re-weighted over all of it.
+pub(crate) fn reapply_dual_boost(config: &mut Config, epoch: &mut Epoch, user_stake: &mut UserStake) -> Result<()> {
+    reweight_dual_boost(config, epoch, user_stake, Clock::get()?.unix_timestamp);
+    Ok(())
+}
+
+fn reweight_dual_boost(config: &mut Config, epoch: &mut Epoch, user_stake: &mut UserStake, now: i64) {
+    let new_bonus = config.dual_boost(user_stake.boost_staked, user_stake.staked_amount());
+    let old_bonus = user_stake.dual_boost;
+    if new_bonus == old_bonus {
+        return;
+    }
+    user_stake.dual_boost = new_bonus;
+
+    for entry in user_stake.stake_entries.iter_mut() {
+        reweight_entry(config, epoch, entry, old_bonus, new_bonus, now);
+    }
+}
+
+/// Moves one locked entry's curve from `old_bonus` to `new_bonus` for the rest of the running
+/// epoch, or for all of the next one when the entry has already been settled into it.
+pub(crate) fn reweight_entry(
+    config: &mut Config,
+    epoch: &mut Epoch,
+    entry: &mut StakeEntry,
+    old_bonus: u64,
+    new_bonus: u64,
+    now: i64,
+) {
+    // Pre-staking curves are never paid
+    if old_bonus == new_bonus || config.index == 0 {
+        return;
+    }
+    if entry.is_unbonding() || !entry.is_locked_at(config.index) {
+        return;
+    }
+    let remaining = (epoch.staked_end_time - now).clamp(0, epoch.stake_duration) as u64;
+
+    // Settlement already wrote next epoch's full-length curve into config.total_curve
+    let (curve, total_curve) = if entry.calculated_index == config.index {
+        (entry.base_curve, &mut config.total_curve)
+    } else {
+        (entry.amount * remaining, &mut epoch.total_curve)
+    };
+    if new_bonus > old_bonus {
+        let delta = curve * (new_bonus - old_bonus) / 100;
+        entry.boosted_curve += delta;
+        *total_curve += delta;
+    } else {
+        let delta = (curve * (old_bonus - new_bonus) / 100).min(entry.boosted_curve);
+        entry.boosted_curve -= delta;
+        *total_curve = total_curve.saturating_sub(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Config, Epoch, UserStake) {
+        let mut config: Config = blank(Config::LEN);
+        config.index = 3;
+        config.dual_boost_max = 50;
+        config.dual_boost_ratio_bps = 1_000;
+
+        let mut epoch: Epoch = blank(Epoch::LEN);
+        epoch.index = 3;
+        epoch.staked_start_time = 0;
+        epoch.stake_duration = 100;
+        epoch.staked_end_time = 100;
+
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        let mut entry: StakeEntry = blank(StakeEntry::LEN);
+        entry.amount = 1_000;
+        entry.last_staked_epoch_index = 1;
+        entry.lock_period = 4;
+        entry.multiplier = 100;
+        user_stake.stake_entries.push(entry);
+        (config, epoch, user_stake)
+    }
+
+    #[test]
+    fn boost_mid_epoch_weights_the_rest_of_the_epoch() {
+        let (mut config, mut epoch, mut user_stake) = setup();
+        user_stake.stake_entries[0].boosted_curve = 100_000;
+        epoch.total_curve = 100_000;
+
+        user_stake.boost_staked = 100;
+        reweight_dual_boost(&mut config, &mut epoch, &mut user_stake, 60);
+
+        // 40 of 100 seconds left at +50%
+        assert_eq!(user_stake.dual_boost, 50);
+        assert_eq!(user_stake.stake_entries[0].boosted_curve, 120_000);
+        assert_eq!(epoch.total_curve, 120_000);
+        assert_eq!(config.total_curve, 0);
+    }
+
+    #[test]
+    fn unstaking_after_settlement_takes_the_next_epoch_bonus_back() {
+        let (mut config, mut epoch, mut user_stake) = setup();
+        user_stake.boost_staked = 100;
+        user_stake.dual_boost = 50;
+
+        // Settled for epoch 3 after it ended: next epoch's curve carries the full bonus
+        let entry = &mut user_stake.stake_entries[0];
+        entry.calculated_index = 3;
+        entry.base_curve = 100_000;
+        entry.boosted_curve = 150_000;
+        config.total_curve = 150_000;
+        epoch.total_curve = 90_000;
+
+        user_stake.boost_staked = 0;
+        reweight_dual_boost(&mut config, &mut epoch, &mut user_stake, 150);
+
+        assert_eq!(user_stake.dual_boost, 0);
+        assert_eq!(user_stake.stake_entries[0].boosted_curve, 100_000);
+        assert_eq!(config.total_curve, 100_000);
+        // The finished epoch's shares are already paid and stay as they were
+        assert_eq!(epoch.total_curve, 90_000);
+    }
+
+    #[test]
+    fn unlocked_and_unbonding_entries_keep_their_curves() {
+        let (mut config, mut epoch, mut user_stake) = setup();
+        user_stake.stake_entries[0].lock_period = 1;
+        user_stake.stake_entries[0].boosted_curve = 40_000;
+        epoch.total_curve = 40_000;
+
+        user_stake.boost_staked = 100;
+        reweight_dual_boost(&mut config, &mut epoch, &mut user_stake, 60);
+
+        assert_eq!(user_stake.dual_boost, 50);
+        assert_eq!(user_stake.stake_entries[0].boosted_curve, 40_000);
+        assert_eq!(epoch.total_curve, 40_000);
+    }
+
+    #[test]
+    fn moved_entries_trade_the_sender_bonus_for_the_recipients() {
+        let (mut config, mut epoch, mut user_stake) = setup();
+        let entry = &mut user_stake.stake_entries[0];
+        entry.calculated_index = 3;
+        entry.base_curve = 100_000;
+        entry.boosted_curve = 160_000;
+        config.total_curve = 160_000;
+
+        // Sender carried +60%, recipient only +10%
+        reweight_entry(&mut config, &mut epoch, entry, 60, 10, 150);
+
+        assert_eq!(entry.boosted_curve, 110_000);
+        assert_eq!(config.total_curve, 110_000);
+        assert_eq!(epoch.total_curve, 0);
+    }
+}
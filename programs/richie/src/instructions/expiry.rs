@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{ constants::*, error::RichieError, events::ExpiredRewardsSwept, state::* };
+
+#[derive(Accounts)]
+pub struct SweepExpiredRewards<'info> {
+    pub cranker: Signer<'info>, // anyone may sweep
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury: Option<Box<Account<'info, TokenAccount>>>, // required when config.expired_reward_treasury is set
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Removes rewards that were not claimed within `claim_expiry_epochs` of their settlement,
+/// either rolling them into the next epoch or paying them to the configured treasury.
+/// Expired rewards stay claimable until swept. Rewards held on receipt entries are exempt: the
+/// receipt may sit with a buyer or in cold storage, and they leave with the entry at withdrawal.
+pub fn sweep_expired_rewards(ctx: Context<SweepExpiredRewards>) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(config.claim_expiry_epochs > 0, RichieError::ClaimExpiryDisabled);
+    // The liquid pool's rewards belong to its share holders and are compounded, not claimed
+    require_keys_neq!(user_stake.owner, config_key, RichieError::InvalidUserStake);
+
+    let amount = user_stake.expire_rewards(config.index, config.claim_expiry_epochs);
+    require!(amount > 0, RichieError::NothingToSweep);
+
+    let treasury_key = route_expired_rewards(
+        config,
+        ctx.bumps.config,
+        &ctx.accounts.reward_vault,
+        ctx.accounts.treasury.as_deref(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(ExpiredRewardsSwept {
+        owner: user_stake.owner,
+        amount,
+        treasury: treasury_key,
+    });
+
+    Ok(())
+}
+
+/// Rolls `amount` of expired rewards into the next epoch, or pays it from reward_vault to
+/// config.expired_reward_treasury when one is set. Returns the treasury used.
+pub(crate) fn route_expired_rewards<'info>(
+    config: &mut Account<'info, Config>,
+    config_bump: u8,
+    reward_vault: &Account<'info, TokenAccount>,
+    treasury: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<Pubkey> {
+    let treasury_key = config.expired_reward_treasury;
+    if treasury_key == Pubkey::default() {
+        // Tokens stay in reward_vault and are paid out again from the next epoch
+        config.swept_rewards += amount;
+    } else {
+        let treasury = treasury.ok_or(RichieError::InvalidTreasury)?;
+        require_keys_eq!(treasury.key(), treasury_key, RichieError::InvalidTreasury);
+
+        let seeds = &[CONFIG.as_bytes(), &[config_bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: reward_vault.to_account_info(),
+            to: treasury.to_account_info(),
+            authority: config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, amount)?;
+    }
+    Ok(treasury_key)
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // position owner; recording a weight locks the position against transfers
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), payer.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            VOTER_WEIGHT_RECORD.as_bytes(),
+            config.realm.as_ref(),
+            config.governing_token_mint.as_ref(),
+            user_stake.owner.as_ref()
+        ],
+        bump,
+        space = 8 + VoterWeightRecord::LEN
+    )]
+    pub voter_weight_record: Box<Account<'info, VoterWeightRecord>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            MAX_VOTER_WEIGHT_RECORD.as_bytes(),
+            config.realm.as_ref(),
+            config.governing_token_mint.as_ref()
+        ],
+        bump,
+        space = 8 + MaxVoterWeightRecord::LEN
+    )]
+    pub max_voter_weight_record: Box<Account<'info, MaxVoterWeightRecord>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_voter_weight_record(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(config.realm != Pubkey::default(), RichieError::GovernanceNotConfigured);
+
+    // Weights decay every epoch, so records are only valid for the slot they were written in
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    voter_weight_record.realm = config.realm;
+    voter_weight_record.governing_token_mint = config.governing_token_mint;
+    voter_weight_record.governing_token_owner = user_stake.owner;
+    voter_weight_record.voter_weight = user_stake.voting_power(config.index);
+    voter_weight_record.voter_weight_expiry = Some(clock.slot);
+    voter_weight_record.weight_action = None;
+    voter_weight_record.weight_action_target = None;
+
+    // The weight may be used to vote, so the stake behind it stays with this owner for the vote lock
+    user_stake.vote_locked_until = user_stake.vote_locked_until.max(clock.unix_timestamp + config.vote_lock);
+
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+    max_voter_weight_record.realm = config.realm;
+    max_voter_weight_record.governing_token_mint = config.governing_token_mint;
+    max_voter_weight_record.max_voter_weight = config.total_voting_power();
+    max_voter_weight_record.max_voter_weight_expiry = Some(clock.slot);
+
+    msg!(
+        "🗳️ Voter weight {} of {} for {}",
+        voter_weight_record.voter_weight,
+        max_voter_weight_record.max_voter_weight,
+        user_stake.owner
+    );
+
+    Ok(())
+}
@@ -73,6 +73,34 @@ pub struct InitializeRewardVault<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct AddRewardMint<'info> {
+    #[account(
+       mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [REWARD.as_bytes(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = config,
+    )]
+    pub extra_reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct ManageConfig<'info> {
     #[account(
@@ -111,6 +139,38 @@ pub fn initialize_stake_vault(
 
     // Set default multipliers (100% base, 120%, 150%, etc.)
     config.multiplier = vec![100, 120, 150, 200, 300];
+    config.liquid_mint = Pubkey::default();
+    config.unbonding_period = 0;
+    config.ve_index = 0;
+    config.ve_locked_amount = 0;
+    config.ve_end_sum = 0;
+    config.ve_expiring = [0; Config::VE_SLOTS];
+    config.realm = Pubkey::default();
+    config.governing_token_mint = Pubkey::default();
+    config.emission_policy = EmissionPolicy::Manual;
+    config.reward_funders = vec![];
+    config.claim_expiry_epochs = 0;
+    config.expired_reward_treasury = Pubkey::default();
+    config.swept_rewards = 0;
+    config.extra_reward_mints = vec![];
+    config.fee_bps = 0;
+    config.treasurer = Pubkey::default();
+    config.treasury = Pubkey::default();
+    config.accrued_fees = 0;
+    config.referral_bps = 0;
+    config.loyalty_tiers = vec![];
+    config.nft_boost = 0;
+    config.nft_boost_allowlist = vec![];
+    config.boost_token_mint = Pubkey::default();
+    config.boost_vault = Pubkey::default();
+    config.dual_boost_max = 0;
+    config.dual_boost_ratio_bps = 0;
+    config.nft_collection = Pubkey::default();
+    config.nft_weight = 0;
+    config.nft_penalty = 0;
+    config.stake_for_minimum = 0;
+    config.vote_lock = 0;
+    config.native_penalties = 0;
 
     Ok(())
 }
@@ -128,6 +188,24 @@ pub fn initialize_reward_vault(
     Ok(())
 }
 
+/// Registers a co-incentive mint; slots are never reused, so a mint cannot be removed again.
+pub fn add_reward_mint(
+    ctx: Context<AddRewardMint>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(config.extra_reward_mints.len() < Config::MAX_EXTRA_REWARDS, RichieError::TooManyRewardMints);
+    require!(
+        reward_mint != config.reward_token_mint && !config.extra_reward_mints.contains(&reward_mint),
+        RichieError::RewardMintRegistered
+    );
+
+    config.extra_reward_mints.push(reward_mint);
+
+    Ok(())
+}
+
 pub fn update_epoch_duration(
     ctx: Context<ManageConfig>,
     duration: i64
@@ -140,6 +218,195 @@ pub fn update_epoch_duration(
     Ok(())
 }
 
+pub fn update_unbonding_period(
+    ctx: Context<ManageConfig>,
+    period: i64
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(period >= 0, RichieError::InvalidUnbondingPeriod);
+
+    config.unbonding_period = period;
+
+    Ok(())
+}
+
+/// `vote_lock` should cover the realm's voting time, so stake that voted cannot be moved and vote again.
+pub fn update_governance(
+    ctx: Context<ManageConfig>,
+    realm: Pubkey,
+    governing_token_mint: Pubkey,
+    vote_lock: i64
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(vote_lock >= 0, RichieError::InvalidVoteLock);
+
+    config.realm = realm;
+    config.governing_token_mint = governing_token_mint;
+    config.vote_lock = vote_lock;
+
+    Ok(())
+}
+
+pub fn update_emission_policy(
+    ctx: Context<ManageConfig>,
+    policy: EmissionPolicy
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(policy.is_valid(), RichieError::InvalidEmissionPolicy);
+
+    config.emission_policy = policy;
+
+    Ok(())
+}
+
+pub fn update_reward_funders(
+    ctx: Context<ManageConfig>,
+    funders: Vec<Pubkey>
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(funders.len() <= Config::MAX_REWARD_FUNDERS, RichieError::TooManyFunders);
+
+    config.reward_funders = funders;
+
+    Ok(())
+}
+
+pub fn update_claim_expiry(
+    ctx: Context<ManageConfig>,
+    expiry_epochs: u64,
+    treasury: Pubkey
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+
+    config.claim_expiry_epochs = expiry_epochs;
+    config.expired_reward_treasury = treasury;
+
+    Ok(())
+}
+
+pub fn update_protocol_fee(
+    ctx: Context<ManageConfig>,
+    fee_bps: u16,
+    treasurer: Pubkey,
+    treasury: Pubkey
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(fee_bps <= Config::MAX_FEE_BPS, RichieError::InvalidFee);
+
+    config.fee_bps = fee_bps;
+    config.treasurer = treasurer;
+    config.treasury = treasury;
+
+    Ok(())
+}
+
+pub fn update_referral_bps(
+    ctx: Context<ManageConfig>,
+    referral_bps: u16
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(referral_bps <= Config::MAX_REFERRAL_BPS, RichieError::InvalidFee);
+
+    config.referral_bps = referral_bps;
+
+    Ok(())
+}
+
+pub fn update_loyalty_tiers(
+    ctx: Context<ManageConfig>,
+    tiers: Vec<LoyaltyTier>
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(
+        tiers.len() <= Config::MAX_LOYALTY_TIERS
+            && tiers.windows(2).all(|pair| pair[0].min_streak < pair[1].min_streak)
+            && tiers.iter().all(|tier| tier.bonus <= Config::MAX_BONUS),
+        RichieError::InvalidLoyaltyTiers
+    );
+
+    config.loyalty_tiers = tiers;
+
+    Ok(())
+}
+
+pub fn update_nft_boost(
+    ctx: Context<ManageConfig>,
+    boost: u64,
+    allowlist: Vec<Pubkey>
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(allowlist.len() <= Config::MAX_NFT_BOOST_ALLOWLIST, RichieError::TooManyNftKeys);
+    require!(boost <= Config::MAX_BONUS, RichieError::InvalidNftBoost);
+
+    config.nft_boost = boost;
+    config.nft_boost_allowlist = allowlist;
+
+    Ok(())
+}
+
+pub fn update_dual_boost(
+    ctx: Context<ManageConfig>,
+    max_bonus: u64,
+    ratio_bps: u64
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(config.boost_token_mint != Pubkey::default(), RichieError::BoostNotConfigured);
+    require!(max_bonus == 0 || ratio_bps > 0, RichieError::InvalidFee);
+    require!(max_bonus <= Config::MAX_BONUS, RichieError::InvalidFee);
+
+    config.dual_boost_max = max_bonus;
+    config.dual_boost_ratio_bps = ratio_bps;
+
+    Ok(())
+}
+
+pub fn update_stake_for_minimum(
+    ctx: Context<ManageConfig>,
+    minimum: u64
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+
+    config.stake_for_minimum = minimum;
+
+    Ok(())
+}
+
+/// Switches the pool between fungible staking and staking NFTs of `collection`.
+/// The pool type can only change while nothing is staked.
+pub fn update_nft_pool(
+    ctx: Context<ManageConfig>,
+    collection: Pubkey,
+    weight: u64,
+    penalty: u64
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(
+        config.nft_collection == collection || config.total_staked == 0,
+        RichieError::InvalidPoolType
+    );
+    require!(collection == Pubkey::default() || weight > 0, RichieError::InvalidNft);
+    // Stakers entered under the current penalty, so it may only go down until they leave
+    require!(penalty <= config.nft_penalty || config.total_staked == 0, RichieError::InvalidNftPenalty);
+
+    config.nft_collection = collection;
+    config.nft_weight = weight;
+    config.nft_penalty = penalty;
+
+    Ok(())
+}
+
 pub fn update_multiplier(
     ctx: Context<ManageConfig>,
     new_multiplier: Vec<u64>,
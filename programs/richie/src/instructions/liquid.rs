@@ -0,0 +1,510 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, burn, mint_to, Burn, Mint, MintTo, Token, TokenAccount, Transfer}
+};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::stake::{ get_multiplier, record_stake };
+
+/// Liquid deposits always use the lowest lock tier.
+const LIQUID_LOCK_PERIOD: u8 = 1;
+
+#[derive(Accounts)]
+pub struct InitializeLiquid<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.stake_token_mint)]
+    pub stake_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [LIQUID.as_bytes()],
+        bump,
+        mint::decimals = stake_token_mint.decimals,
+        mint::authority = config,
+    )]
+    pub liquid_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [USER.as_bytes(), config.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub liquid_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct StakeLiquid<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub liquid_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUID.as_bytes()],
+        bump
+    )]
+    pub liquid_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = liquid_mint,
+        associated_token::authority = user
+    )]
+    pub user_liquid_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub from_token_account: Box<Account<'info, TokenAccount>>, // user's $RICHIE
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemLiquid<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub liquid_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUID.as_bytes()],
+        bump
+    )]
+    pub liquid_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = liquid_mint,
+        token::authority = user
+    )]
+    pub user_liquid_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub to_token_account: Box<Account<'info, TokenAccount>>, // user's $RICHIE
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub user_stake: Option<Box<Account<'info, UserStake>>>, // receives the unbonding entry when the pool has a cooldown
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct CompoundLiquid<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub liquid_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_liquid(ctx: Context<InitializeLiquid>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let liquid_stake = &mut ctx.accounts.liquid_stake;
+    let stakes = &mut ctx.accounts.stakes;
+
+    require_keys_eq!(ctx.accounts.admin.key(), config.admin, RichieError::UnAuthorized);
+
+    config.liquid_mint = ctx.accounts.liquid_mint.key();
+
+    // The pool position is owned by the config PDA and settled like any other staker
+    liquid_stake.owner = config.key();
+    require!(stakes.list.len() < Stakes::MAX_USERS, RichieError::TooManyStakers);
+    stakes.list.push(liquid_stake.key());
+
+    Ok(())
+}
+
+pub fn stake_liquid(ctx: Context<StakeLiquid>, index: u64, amount: u64) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    require!(ctx.accounts.config.liquid_mint != Pubkey::default(), RichieError::LiquidNotInitialized);
+
+    let principal = liquid_principal(&ctx.accounts.config, &ctx.accounts.liquid_stake);
+    let supply = ctx.accounts.liquid_mint.supply;
+    let shares = if supply == 0 || principal == 0 {
+        amount
+    } else {
+        (amount as u128 * supply as u128 / principal as u128) as u64
+    };
+    require!(shares > 0, RichieError::InsufficientStake);
+
+    record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.liquid_stake,
+        config_key,
+        index,
+        amount,
+        LIQUID_LOCK_PERIOD,
+        Pubkey::default(),
+        Pubkey::default(),
+        None,
+    )?;
+    compact_liquid_position(&mut ctx.accounts.liquid_stake, index)?;
+
+    // Transfer tokens
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.from_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.liquid_mint.to_account_info(),
+        to: ctx.accounts.user_liquid_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    mint_to(cpi_ctx, shares)?;
+
+    msg!("💧 Minted {} shares for {} staked", shares, amount);
+
+    Ok(())
+}
+
+pub fn redeem_liquid(ctx: Context<RedeemLiquid>, shares: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let liquid_stake = &mut ctx.accounts.liquid_stake;
+
+    let principal = liquid_principal(config, liquid_stake);
+    let supply = ctx.accounts.liquid_mint.supply;
+    require!(shares > 0 && supply > 0, RichieError::NothingToWithdraw);
+
+    let underlying = (shares as u128 * principal as u128 / supply as u128) as u64;
+    require!(underlying > 0, RichieError::NothingToWithdraw);
+
+    // The redeemer's part of the pending reward is paid from principal; the reward itself
+    // compounds for the remaining holders, so the exchange rate is unchanged.
+    // Redemptions are served from entries whose lock has ended, so they never pay a penalty
+    let mut remaining = underlying;
+    for entry in liquid_stake.stake_entries.iter_mut() {
+        if remaining == 0 {
+            break;
+        }
+        if entry.lock_end() > config.index {
+            continue;
+        }
+
+        let take = remaining.min(entry.amount);
+        let base_removed = (entry.base_curve as u128 * take as u128 / entry.amount as u128) as u64;
+        let boosted_removed = (entry.boosted_curve as u128 * take as u128 / entry.amount as u128) as u64;
+
+        entry.amount -= take;
+        entry.base_curve -= base_removed;
+        entry.boosted_curve -= boosted_removed;
+        // Once settled the entry's curve is next epoch's and sits in config.total_curve
+        if entry.calculated_index == config.index {
+            config.total_curve = config.total_curve.saturating_sub(boosted_removed);
+        } else {
+            epoch.total_curve = epoch.total_curve.saturating_sub(base_removed);
+        }
+
+        remaining -= take;
+    }
+    require!(remaining == 0, RichieError::InsufficientStake);
+
+    liquid_stake.stake_entries.retain(|e| e.amount > 0);
+    config.total_staked -= underlying;
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.liquid_mint.to_account_info(),
+            from: ctx.accounts.user_liquid_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    burn(burn_ctx, shares)?;
+
+    // With a cooldown the underlying waits as an unbonding entry of the redeemer, paid by complete_unstake
+    if config.unbonding_period > 0 {
+        let user_stake = ctx.accounts.user_stake.as_mut().ok_or(RichieError::UnbondingRequired)?;
+        queue_redemption(config, &mut ctx.accounts.stakes, user_stake, ctx.accounts.user.key(), underlying)?;
+
+        msg!("💧 Redeemed {} shares, {} unbonding", shares, underlying);
+        return Ok(());
+    }
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.stake_vault.to_account_info(),
+        to: ctx.accounts.to_token_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, underlying)?;
+
+    msg!("💧 Redeemed {} shares for {}", shares, underlying);
+
+    Ok(())
+}
+
+pub fn compound_liquid(ctx: Context<CompoundLiquid>, index: u64) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    require!(
+        ctx.accounts.config.reward_token_mint == ctx.accounts.config.stake_token_mint,
+        RichieError::CannotCompound
+    );
+
+    require!(ctx.accounts.liquid_stake.pending_reward > 0, RichieError::NoReward);
+    let amount = ctx.accounts.liquid_stake.take_pending_reward();
+
+    // Restaking the pool's rewards raises principal without minting shares, lifting the exchange rate
+    record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.liquid_stake,
+        config_key,
+        index,
+        amount,
+        LIQUID_LOCK_PERIOD,
+        Pubkey::default(),
+        Pubkey::default(),
+        None,
+    )?;
+    compact_liquid_position(&mut ctx.accounts.liquid_stake, index)?;
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, amount)?;
+
+    msg!("💧 Compounded {} reward into the liquid pool", amount);
+
+    Ok(())
+}
+
+/// Books `amount` redeemed from the pool as an unbonding entry of `owner`. The entry is already out
+/// of the pool; cancel_unstake turns it into a regular lowest-tier entry.
+fn queue_redemption(config: &Config, stakes: &mut Stakes, user_stake: &mut Account<UserStake>, owner: Pubkey, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    if user_stake.owner == Pubkey::default() {
+        user_stake.owner = owner;
+    }
+    if !stakes.list.contains(&user_stake.key()) {
+        require!(stakes.list.len() < Stakes::MAX_USERS, RichieError::TooManyStakers);
+        stakes.list.push(user_stake.key());
+    }
+    require!(user_stake.stake_entries.len() < UserStake::MAX_ENTRIES, RichieError::TooManyEntries);
+
+    let id = user_stake.allocate_entry_id();
+    user_stake.stake_entries.push(StakeEntry {
+        id,
+        amount,
+        last_staked_epoch_index: config.index,
+        lock_period: LIQUID_LOCK_PERIOD,
+        multiplier: get_multiplier(config, LIQUID_LOCK_PERIOD)?,
+        base_curve: 0,
+        boosted_curve: 0,
+        calculated_index: 0,
+        receipt_mint: Pubkey::default(),
+        pending_reward: 0,
+        extra_pending_rewards: [0; Config::MAX_EXTRA_REWARDS],
+        unbond_release_time: clock.unix_timestamp + config.unbonding_period,
+        unbond_penalty: 0,
+        nft_mint: Pubkey::default(),
+    });
+
+    Ok(())
+}
+
+/// Entries with the same key can always be merged: (lock ended, settled index, start epoch, multiplier).
+type MergeKey = (bool, u64, u64, u64);
+
+/// Value backing the share supply: principal plus rewards settled to the pool but not yet compounded.
+/// Counting pending rewards keeps deposits made just before compound_liquid from diluting existing holders.
+fn liquid_principal(config: &Config, liquid_stake: &UserStake) -> u64 {
+    let principal: u64 = liquid_stake.stake_entries.iter().map(|e| e.amount).sum();
+    if config.reward_token_mint == config.stake_token_mint {
+        principal + liquid_stake.pending_reward
+    } else {
+        principal
+    }
+}
+
+/// Keeps the pool position small by folding together deposits made in the same epoch
+/// and entries whose lock has ended.
+fn compact_liquid_position(liquid_stake: &mut UserStake, current_index: u64) -> Result<()> {
+    let mut groups: Vec<(MergeKey, Vec<u64>)> = Vec::new();
+    for entry in liquid_stake.stake_entries.iter() {
+        let key = if entry.lock_end() <= current_index {
+            (true, entry.calculated_index, 0, 0)
+        } else {
+            (false, entry.calculated_index, entry.last_staked_epoch_index, entry.multiplier)
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, ids)) => ids.push(entry.id),
+            None => groups.push((key, vec![entry.id])),
+        }
+    }
+
+    for (_, ids) in groups.iter().filter(|(_, ids)| ids.len() >= 2) {
+        liquid_stake.merge_entries(ids, current_index)?;
+    }
+
+    Ok(())
+}
@@ -2,8 +2,36 @@ pub mod initialize;
 pub mod stake;
 pub mod toggle;
 pub mod claim;
+pub mod position;
+pub mod transfer;
+pub mod receipt;
+pub mod liquid;
+pub mod unbond;
+pub mod voting;
+pub mod governance;
+pub mod schedule;
+pub mod expiry;
+pub mod distributor;
+pub mod nft_boost;
+pub mod dual_boost;
+pub mod nft_pool;
+pub mod native;
 
 pub use initialize::*;
 pub use stake::*;
 pub use claim::*;
-pub use toggle::*;
\ No newline at end of file
+pub use toggle::*;
+pub use position::*;
+pub use transfer::*;
+pub use receipt::*;
+pub use liquid::*;
+pub use unbond::*;
+pub use voting::*;
+pub use governance::*;
+pub use schedule::*;
+pub use expiry::*;
+pub use distributor::*;
+pub use nft_boost::*;
+pub use dual_boost::*;
+pub use nft_pool::*;
+pub use native::*;
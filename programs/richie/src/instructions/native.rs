@@ -0,0 +1,408 @@
+use anchor_lang::{ prelude::*, system_program };
+
+use anchor_spl::token::{
+    close_account, spl_token::native_mint, sync_native, transfer, CloseAccount, Mint, SyncNative, Token, TokenAccount, Transfer,
+};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::{ claim::release_entry, stake::record_stake };
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct StakeSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        constraint = config.stake_token_mint == native_mint::ID @ RichieError::NotNativeMint
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = config.stake_token_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        constraint = config.stake_token_mint == native_mint::ID @ RichieError::NotNativeMint
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [UNWRAP.as_bytes(), user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub unwrap_account: Box<Account<'info, TokenAccount>>, // opened and closed within the instruction
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteUnstakeSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        constraint = config.stake_token_mint == native_mint::ID @ RichieError::NotNativeMint
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [UNWRAP.as_bytes(), user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub unwrap_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawNativePenalties<'info> {
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = native_mint::ID,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = native_mint::ID
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>, // wrapped SOL account picked by the treasurer
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        constraint = config.reward_token_mint == native_mint::ID @ RichieError::NotNativeMint
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [UNWRAP.as_bytes(), user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = config
+    )]
+    pub unwrap_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Stakes `amount` lamports in a pool whose stake mint is the native mint, wrapping them in stake_vault.
+pub fn stake_sol(ctx: Context<StakeSol>, index: u64, amount: u64, lock_period: u8) -> Result<()> {
+    let owner = ctx.accounts.user.key();
+
+    record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.user_stake,
+        owner,
+        index,
+        amount,
+        lock_period,
+        Pubkey::default(),
+        Pubkey::default(),
+        ctx.accounts.boost_nft_account.as_ref(),
+    )?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.user.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+    };
+    system_program::transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts), amount)?;
+
+    // Lamports sent to the vault outside this instruction are picked up here as well,
+    // but only `amount` is booked to the position
+    let cpi_accounts = SyncNative {
+        account: ctx.accounts.stake_vault.to_account_info(),
+    };
+    sync_native(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts))?;
+
+    msg!("◎ Staked {} lamports", amount);
+
+    Ok(())
+}
+
+/// `withdraw` for native pools, paying the principal out as SOL. The token program cannot burn
+/// wrapped SOL, so an early-exit penalty stays in stake_vault, booked to config.native_penalties.
+pub fn withdraw_sol(ctx: Context<WithdrawSol>, entry_id: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(config.unbonding_period == 0, RichieError::UnbondingRequired);
+    let entry = user_stake.remove_entry(entry_id)?;
+    require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+    require!(!entry.is_nft(), RichieError::NftEntry);
+    let (total_withdraw, total_penalty) = release_entry(config, epoch, user_stake, &entry)?;
+    require!(total_withdraw > 0, RichieError::NothingToWithdraw);
+    config.native_penalties += total_penalty;
+
+    unwrap_to_user(
+        &ctx.accounts.token_program,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.unwrap_account,
+        &ctx.accounts.user,
+        &ctx.accounts.config,
+        ctx.bumps.config,
+        total_withdraw,
+    )?;
+
+    msg!("◎ Withdrew {} lamports from entry {}, penalty {}", total_withdraw, entry_id, total_penalty);
+
+    Ok(())
+}
+
+/// `complete_unstake` for native pools, paying the principal out as SOL. Receipt entries go
+/// through complete_unstake, which pays their holder in wrapped SOL.
+pub fn complete_unstake_sol(ctx: Context<CompleteUnstakeSol>, entry_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    let entry = user_stake.remove_entry(entry_id)?;
+    require!(entry.is_unbonding(), RichieError::EntryNotUnbonding);
+    require!(clock.unix_timestamp >= entry.unbond_release_time, RichieError::UnbondingNotFinished);
+    require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+    require!(!entry.is_nft(), RichieError::NftEntry);
+
+    let payout = entry.amount - entry.unbond_penalty;
+    config.native_penalties += entry.unbond_penalty;
+
+    unwrap_to_user(
+        &ctx.accounts.token_program,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.unwrap_account,
+        &ctx.accounts.user,
+        &ctx.accounts.config,
+        ctx.bumps.config,
+        payout,
+    )?;
+
+    msg!("◎ Entry {} unbonded, paid out {} lamports", entry_id, payout);
+
+    Ok(())
+}
+
+/// Moves the wrapped SOL penalties collected in stake_vault to a treasurer-chosen account.
+pub fn withdraw_native_penalties(ctx: Context<WithdrawNativePenalties>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        config.treasurer != Pubkey::default() && config.treasurer == ctx.accounts.treasurer.key(),
+        RichieError::UnAuthorized
+    );
+
+    let amount = std::mem::take(&mut config.native_penalties);
+    require!(amount > 0, RichieError::NothingToWithdraw);
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.stake_vault.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+/// `claim` of the primary reward for pools whose reward mint is the native mint, paid out as SOL.
+pub fn claim_sol(ctx: Context<ClaimSol>) -> Result<()> {
+    let amount = ctx.accounts.user_stake.take_pending_reward();
+    require!(amount > 0, RichieError::NoReward);
+
+    unwrap_to_user(
+        &ctx.accounts.token_program,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.unwrap_account,
+        &ctx.accounts.user,
+        &ctx.accounts.config,
+        ctx.bumps.config,
+        amount,
+    )?;
+
+    msg!("◎ Claimed {} lamports", amount);
+
+    Ok(())
+}
+
+/// Moves `amount` wrapped SOL from `vault` into the fresh `unwrap_account` and closes it to `user`.
+/// Only the transferred token amount leaves the vault; its rent-exempt reserve is never touched,
+/// and the unwrap account's own rent goes back to the user who paid it.
+fn unwrap_to_user<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    unwrap_account: &Account<'info, TokenAccount>,
+    user: &Signer<'info>,
+    config: &Account<'info, Config>,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: vault.to_account_info(),
+        to: unwrap_account.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    transfer(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer), amount)?;
+
+    let cpi_accounts = CloseAccount {
+        account: unwrap_account.to_account_info(),
+        destination: user.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer))
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    metadata::{ mpl_token_metadata::accounts::Metadata, MetadataAccount },
+    token::{ Mint, TokenAccount },
+};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::position::ManagePosition;
+
+#[derive(Accounts)]
+pub struct RegisterNftBoost<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        constraint = nft_mint.decimals == 0 && nft_mint.supply == 1 @ RichieError::InvalidNftBoost
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        token::mint = nft_mint,
+        token::authority = user,
+        constraint = nft_token_account.amount == 1 @ RichieError::InvalidNftBoost
+    )]
+    pub nft_token_account: Box<Account<'info, TokenAccount>>, // stays in the user's wallet
+
+    #[account(address = Metadata::find_pda(&nft_mint.key()).0)]
+    pub nft_metadata: Option<Box<Account<'info, MetadataAccount>>>, // not needed when the mint itself is allowlisted
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [NFT_BOOST_LOCK.as_bytes(), nft_mint.key().as_ref()],
+        bump,
+        space = 8 + NftBoostLock::LEN
+    )]
+    pub nft_boost_lock: Box<Account<'info, NftBoostLock>>,
+
+    #[account(mut)]
+    pub previous_user_stake: Option<Box<Account<'info, UserStake>>>, // required when the NFT is still registered on another position
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Boosts the user's locked entries from the next settlement on, for as long as `nft_token_account`
+/// keeps holding an NFT whose mint, verified collection or verified creator is on the config allowlist.
+/// The NFT moves off whichever position it was registered on before; that holder no longer has it.
+pub fn register_nft_boost(ctx: Context<RegisterNftBoost>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let nft_mint = ctx.accounts.nft_mint.key();
+    require!(config.nft_boost > 0, RichieError::InvalidNftBoost);
+
+    let allowlisted = config.nft_boost_allowlist.contains(&nft_mint)
+        || ctx.accounts.nft_metadata.as_ref().is_some_and(|metadata| {
+            let in_collection = metadata
+                .collection
+                .as_ref()
+                .is_some_and(|collection| collection.verified && config.nft_boost_allowlist.contains(&collection.key));
+            let by_creator = metadata.creators.as_ref().is_some_and(|creators| {
+                creators.iter().any(|creator| creator.verified && config.nft_boost_allowlist.contains(&creator.address))
+            });
+            in_collection || by_creator
+        });
+    require!(allowlisted, RichieError::InvalidNftBoost);
+
+    let user_stake = &mut ctx.accounts.user_stake;
+    let nft_boost_lock = &mut ctx.accounts.nft_boost_lock;
+    if nft_boost_lock.user_stake != Pubkey::default() && nft_boost_lock.user_stake != user_stake.key() {
+        let previous = ctx.accounts.previous_user_stake.as_mut().ok_or(RichieError::InvalidNftBoost)?;
+        require_keys_eq!(previous.key(), nft_boost_lock.user_stake, RichieError::InvalidNftBoost);
+        // The previous position may have registered another NFT since
+        if previous.boost_nft_mint == nft_mint {
+            previous.boost_nft_mint = Pubkey::default();
+            previous.boost_nft_account = Pubkey::default();
+        }
+    }
+    nft_boost_lock.user_stake = user_stake.key();
+
+    user_stake.boost_nft_mint = nft_mint;
+    user_stake.boost_nft_account = ctx.accounts.nft_token_account.key();
+
+    msg!("🖼️ NFT {} registered for boost", nft_mint);
+
+    Ok(())
+}
+
+pub fn unregister_nft_boost(ctx: Context<ManagePosition>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    require!(user_stake.boost_nft_mint != Pubkey::default(), RichieError::InvalidNftBoost);
+
+    user_stake.boost_nft_mint = Pubkey::default();
+    user_stake.boost_nft_account = Pubkey::default();
+
+    Ok(())
+}
+
+/// True while the registered token account still holds the boost NFT for the position owner.
+/// A closed or emptied account reads as no longer held.
+pub(crate) fn nft_boost_held(user_stake: &UserStake, nft_account: &AccountInfo) -> bool {
+    if nft_account.key() != user_stake.boost_nft_account || *nft_account.owner != anchor_spl::token::ID {
+        return false;
+    }
+    let Ok(token_account) = TokenAccount::try_deserialize(&mut &nft_account.data.borrow()[..]) else {
+        return false;
+    };
+    token_account.mint == user_stake.boost_nft_mint
+        && token_account.owner == user_stake.owner
+        && token_account.amount == 1
+}
+
+/// Drops the position's NFT registration if `nft_account` no longer holds the NFT. The account is
+/// required while an NFT is registered.
+pub(crate) fn refresh_nft_boost(user_stake: &mut UserStake, nft_account: Option<&AccountInfo>) -> Result<()> {
+    if user_stake.boost_nft_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let nft_account = nft_account.ok_or(RichieError::InvalidNftBoost)?;
+    require_keys_eq!(nft_account.key(), user_stake.boost_nft_account, RichieError::InvalidNftBoost);
+    if !nft_boost_held(user_stake, nft_account) {
+        user_stake.boost_nft_mint = Pubkey::default();
+        user_stake.boost_nft_account = Pubkey::default();
+        msg!("🖼️ Boost NFT left the wallet, boost dropped");
+    }
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    metadata::{ mpl_token_metadata::accounts::Metadata, MetadataAccount },
+    token::{ close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::{ claim::release_entry, stake::record_stake };
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct StakeNft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        constraint = nft_mint.decimals == 0 && nft_mint.supply == 1 @ RichieError::InvalidNft
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = Metadata::find_pda(&nft_mint.key()).0)]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = user
+    )]
+    pub from_nft_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [NFT_ESCROW.as_bytes(), nft_mint.key().as_ref()],
+        bump,
+        token::mint = nft_mint,
+        token::authority = config
+    )]
+    pub nft_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Box<Account<'info, Stakes>>,
+
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawNft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_ESCROW.as_bytes(), nft_mint.key().as_ref()],
+        bump,
+        token::mint = nft_mint,
+        token::authority = config
+    )]
+    pub nft_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = nft_mint
+    )]
+    pub to_nft_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump,
+        token::mint = config.reward_token_mint,
+        token::authority = config
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = config.reward_token_mint,
+        token::authority = user
+    )]
+    pub penalty_token_account: Option<Box<Account<'info, TokenAccount>>>, // user's reward tokens, only for early exits
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Escrows one NFT of the pool collection and books it as a stake entry worth `config.nft_weight`.
+pub fn stake_nft(ctx: Context<StakeNft>, index: u64, lock_period: u8) -> Result<()> {
+    let nft_mint = ctx.accounts.nft_mint.key();
+    let collection = ctx.accounts.config.nft_collection;
+    require!(collection != Pubkey::default(), RichieError::InvalidPoolType);
+
+    let in_collection = ctx
+        .accounts
+        .nft_metadata
+        .collection
+        .as_ref()
+        .is_some_and(|c| c.verified && c.key == collection);
+    require!(in_collection, RichieError::InvalidNft);
+
+    let owner = ctx.accounts.user.key();
+    let weight = ctx.accounts.config.nft_weight;
+    let entry_id = record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.user_stake,
+        owner,
+        index,
+        weight,
+        lock_period,
+        Pubkey::default(),
+        nft_mint,
+        ctx.accounts.boost_nft_account.as_ref(),
+    )?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.from_nft_account.to_account_info(),
+        to: ctx.accounts.nft_escrow.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, 1)?;
+
+    msg!("🖼️ Staked NFT {} as entry {} with weight {}", nft_mint, entry_id, weight);
+
+    Ok(())
+}
+
+/// Reward tokens owed for taking `entry` out before its lock ends.
+pub(crate) fn nft_exit_penalty(config: &Config, entry: &StakeEntry) -> u64 {
+    if config.index < entry.lock_end() {
+        config.nft_penalty
+    } else {
+        0
+    }
+}
+
+/// Returns the escrowed NFT and closes its escrow. With an unbonding period the entry must have
+/// gone through request_unstake first. Leaving before the lock ends costs `config.nft_penalty`
+/// reward tokens, as of the exit request, which roll into the next epoch.
+pub fn withdraw_nft(ctx: Context<WithdrawNft>, entry_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let epoch = &mut ctx.accounts.epoch;
+
+    let entry = user_stake.remove_entry(entry_id)?;
+    require_keys_eq!(entry.nft_mint, ctx.accounts.nft_mint.key(), RichieError::InvalidNft);
+
+    let penalty = if entry.is_unbonding() {
+        // Already out of the pool since request_unstake, which fixed the penalty
+        require!(clock.unix_timestamp >= entry.unbond_release_time, RichieError::UnbondingNotFinished);
+        entry.unbond_penalty
+    } else {
+        require!(config.unbonding_period == 0, RichieError::UnbondingRequired);
+        // The weight-based penalty has nothing to burn; the reward-token penalty replaces it
+        release_entry(config, epoch, user_stake, &entry)?;
+        nft_exit_penalty(config, &entry)
+    };
+    if penalty > 0 {
+        let penalty_token_account = ctx.accounts.penalty_token_account.as_ref().ok_or(RichieError::PenaltyAccountRequired)?;
+        let cpi_accounts = Transfer {
+            from: penalty_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, penalty)?;
+        config.swept_rewards += penalty;
+    }
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.nft_escrow.to_account_info(),
+        to: ctx.accounts.to_nft_account.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    transfer(cpi_ctx, 1)?;
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.nft_escrow.to_account_info(),
+        destination: ctx.accounts.user.to_account_info(),
+        authority: ctx.accounts.config.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer))?;
+
+    msg!("🖼️ Returned NFT {} from entry {}, penalty {}", entry.nft_mint, entry_id, penalty);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_penalty_applies_only_before_the_lock_ends() {
+        let mut config: Config = blank(Config::LEN);
+        config.nft_penalty = 250;
+        let mut entry: StakeEntry = blank(StakeEntry::LEN);
+        entry.nft_mint = Pubkey::new_unique();
+        entry.last_staked_epoch_index = 2;
+        entry.lock_period = 3;
+
+        config.index = 4;
+        assert_eq!(nft_exit_penalty(&config, &entry), 250);
+        config.index = 5;
+        assert_eq!(nft_exit_penalty(&config, &entry), 0);
+
+        // Without a configured penalty a locked exit costs nothing
+        config.index = 2;
+        config.nft_penalty = 0;
+        assert_eq!(nft_exit_penalty(&config, &entry), 0);
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::stake::get_multiplier;
+
+#[derive(Accounts)]
+pub struct ManagePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+#[derive(Accounts)]
+pub struct OperatePosition<'info> {
+    pub authority: Signer<'info>, // owner or operator
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+#[derive(Accounts)]
+pub struct ViewStakeEntry<'info> {
+    pub user_stake: Account<'info, UserStake>,
+}
+
+pub fn set_operator(ctx: Context<ManagePosition>, operator: Pubkey, permissions: u8) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(operator != Pubkey::default(), RichieError::InvalidRecipient);
+    require!(
+        permissions != 0 && permissions & !UserStake::OPERATOR_ALL == 0,
+        RichieError::InvalidPermissions
+    );
+
+    user_stake.operator = operator;
+    user_stake.operator_permissions = permissions;
+
+    msg!("🤖 Operator {} set with permissions {:#05b}", operator, permissions);
+
+    Ok(())
+}
+
+pub fn revoke_operator(ctx: Context<ManagePosition>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    user_stake.operator = Pubkey::default();
+    user_stake.operator_permissions = 0;
+
+    msg!("🤖 Operator revoked");
+
+    Ok(())
+}
+
+pub fn extend_lock(ctx: Context<OperatePosition>, entry_id: u64, lock_period: u8) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(
+        user_stake.is_authorized(ctx.accounts.authority.key(), UserStake::OPERATOR_EXTEND_LOCK),
+        RichieError::UnAuthorized
+    );
+
+    require!(config.index > 0, RichieError::InvalidEpochIndex);
+    let multiplier = get_multiplier(config, lock_period)?;
+
+    let entry = user_stake.entry_mut(entry_id)?;
+    require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+    require!(!entry.is_unbonding(), RichieError::EntryUnbonding);
+    let new_end = config.index + lock_period as u64;
+    let old_end = entry.lock_end();
+    require!(new_end > old_end, RichieError::InvalidLockExtension);
+
+    // The relock starts from the current epoch. Curves for this epoch are already
+    // part of the epoch totals, so the new multiplier applies from the next settlement.
+    entry.last_staked_epoch_index = config.index;
+    entry.lock_period = lock_period;
+    entry.multiplier = multiplier;
+
+    config.remove_voting_lock(entry.amount, old_end);
+    config.add_voting_lock(entry.amount, new_end);
+
+    msg!("🔒 Entry {} relocked until epoch {}", entry_id, new_end);
+
+    Ok(())
+}
+
+pub fn merge_entries(ctx: Context<ManagePosition>, entry_ids: Vec<u64>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    user_stake.merge_entries(&entry_ids, config.index)?;
+
+    msg!("🧩 Merged {} entries into entry {}", entry_ids.len(), entry_ids[0]);
+
+    Ok(())
+}
+
+pub fn get_stake_entry(ctx: Context<ViewStakeEntry>, entry_id: u64) -> Result<StakeEntry> {
+    ctx.accounts.user_stake.entry(entry_id).cloned()
+}
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, burn, mint_to, set_authority, spl_token::instruction::AuthorityType, Burn, Mint, MintTo, SetAuthority, Token, TokenAccount, Transfer}
+};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::claim::{ pay_extra_rewards, pay_out_principal, release_entry };
+
+#[derive(Accounts)]
+pub struct ClaimReceipt<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        token::mint = receipt_mint,
+        token::authority = holder,
+        constraint = holder_receipt_account.amount == 1 @ RichieError::InvalidReceipt
+    )]
+    pub holder_receipt_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = reward_mint,
+        associated_token::authority = holder
+    )]
+    pub holder_reward_account: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReceipt<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(mut)]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = holder,
+        constraint = holder_receipt_account.amount == 1 @ RichieError::InvalidReceipt
+    )]
+    pub holder_receipt_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub stake_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = stake_token_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub to_token_account: Box<Account<'info, TokenAccount>>, // holder's $RICHIE
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = reward_mint,
+        associated_token::authority = holder
+    )]
+    pub holder_reward_account: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints the single receipt token for a freshly staked entry, then drops the mint authority so supply stays at 1.
+pub(crate) fn mint_receipt<'info>(
+    token_program: &Program<'info, Token>,
+    config: &Account<'info, Config>,
+    config_bump: u8,
+    receipt_mint: &Account<'info, Mint>,
+    receipt_account: &Account<'info, TokenAccount>,
+) -> Result<()> {
+    let seeds = &[CONFIG.as_bytes(), &[config_bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: receipt_mint.to_account_info(),
+        to: receipt_account.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    mint_to(cpi_ctx, 1)?;
+
+    let cpi_accounts = SetAuthority {
+        current_authority: config.to_account_info(),
+        account_or_mint: receipt_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    set_authority(cpi_ctx, AuthorityType::MintTokens, None)
+}
+
+/// Pays the receipt holder the entry's rewards selected by `reward_mask`, laid out as in `claim`.
+pub fn claim_receipt<'info>(ctx: Context<'_, '_, '_, 'info, ClaimReceipt<'info>>, entry_id: u64, reward_mask: u8) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    let entry = user_stake.entry_mut(entry_id)?;
+    require_keys_eq!(entry.receipt_mint, ctx.accounts.receipt_mint.key(), RichieError::InvalidReceipt);
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let mut paid = false;
+
+    if reward_mask & Config::PRIMARY_REWARD != 0 && entry.pending_reward > 0 {
+        let amount = std::mem::take(&mut entry.pending_reward);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.holder_reward_account.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, amount)?;
+        paid = true;
+    }
+
+    paid |= pay_extra_rewards(
+        &ctx.accounts.token_program,
+        &ctx.accounts.config,
+        bump,
+        ctx.remaining_accounts,
+        &mut entry.extra_pending_rewards,
+        reward_mask,
+        Some(ctx.accounts.holder.key()),
+    )?;
+
+    require!(paid, RichieError::NoReward);
+
+    Ok(())
+}
+
+/// Extra rewards still on the entry are paid too; pass their vault and destination pairs as in `claim`.
+pub fn withdraw_receipt<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawReceipt<'info>>, entry_id: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(config.unbonding_period == 0, RichieError::UnbondingRequired);
+    let entry = user_stake.remove_entry(entry_id)?;
+    require_keys_eq!(entry.receipt_mint, ctx.accounts.receipt_mint.key(), RichieError::InvalidReceipt);
+
+    let (total_withdraw, total_penalty) = release_entry(config, epoch, user_stake, &entry)?;
+    require!(total_withdraw > 0, RichieError::NothingToWithdraw);
+
+    let bump = ctx.bumps.config;
+    let seeds = &[CONFIG.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    pay_out_principal(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.config,
+        bump,
+        &ctx.accounts.stake_token_mint,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.to_token_account,
+        total_withdraw,
+        total_penalty,
+    )?;
+
+    // The entry is gone, so any rewards it still holds go out with the principal
+    if entry.pending_reward > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.holder_reward_account.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, entry.pending_reward)?;
+    }
+    let mut extra_pending_rewards = entry.extra_pending_rewards;
+    let extra_mask = (0..Config::MAX_EXTRA_REWARDS)
+        .filter(|slot| extra_pending_rewards[*slot] > 0)
+        .fold(0, |mask, slot| mask | Config::extra_reward_bit(slot));
+    pay_extra_rewards(
+        &ctx.accounts.token_program,
+        &ctx.accounts.config,
+        bump,
+        ctx.remaining_accounts,
+        &mut extra_pending_rewards,
+        extra_mask,
+        Some(ctx.accounts.holder.key()),
+    )?;
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            from: ctx.accounts.holder_receipt_account.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info(),
+        },
+    );
+    burn(burn_ctx, 1)?;
+
+    msg!("🔥 Burned receipt {} for entry {}", entry.receipt_mint, entry_id);
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::toggle::open_epoch;
+
+#[derive(Accounts)]
+pub struct FundSchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [SCHEDULE.as_bytes()],
+        bump,
+        space = 8 + EmissionSchedule::LEN
+    )]
+    pub schedule: Box<Account<'info, EmissionSchedule>>,
+
+    #[account(mut)]
+    pub reward_mint_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [SCHEDULE.as_bytes()],
+        bump
+    )]
+    pub schedule: Box<Account<'info, EmissionSchedule>>,
+
+    #[account(
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub previous_epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        seeds = [EPOCH.as_bytes(), &(config.index + 1).to_le_bytes()],
+        bump,
+        space = 8 + Epoch::LEN
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = config.reward_token_mint
+    )]
+    pub cranker_reward_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn fund_schedule(ctx: Context<FundSchedule>, rewards: Vec<u64>, crank_fee: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let schedule = &mut ctx.accounts.schedule;
+
+    require!(ctx.accounts.admin.key() == config.admin, RichieError::UnAuthorized);
+    require!(!rewards.is_empty(), RichieError::InvalidRewardAmount);
+    require!(
+        schedule.queue.len() + rewards.len() <= EmissionSchedule::MAX_EPOCHS,
+        RichieError::ScheduleFull
+    );
+
+    let mut total: u64 = 0;
+    for reward in rewards.iter() {
+        require!(*reward > 0, RichieError::InvalidRewardAmount);
+        total += reward + crank_fee;
+        schedule.queue.push(ScheduledEmission { reward: *reward, crank_fee });
+    }
+
+    // Rewards and crank fees for every funded epoch are escrowed up front
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_mint_token_account.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.admin.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, total)?;
+
+    msg!("📅 Funded {} epochs ({} tokens), {} queued", rewards.len(), total, schedule.queue.len());
+
+    Ok(())
+}
+
+pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let schedule = &mut ctx.accounts.schedule;
+    let epoch = &mut ctx.accounts.epoch;
+
+    require!(
+        clock.unix_timestamp >= config.last_epoch_time + config.epoch_duration,
+        RichieError::EpochTooSoon
+    );
+    require!(!schedule.queue.is_empty(), RichieError::ScheduleEmpty);
+
+    let emission = schedule.queue.remove(0);
+    let index = config.index + 1;
+
+    // Under an emission policy the schedule only escrows funds; the policy sets the amount, which may be zero
+    let wanted = config.emission_policy.reward_for(index).unwrap_or(emission.reward);
+    let reward = schedule.draw(emission.reward, wanted);
+    if reward < wanted {
+        msg!("⚠️ Emission policy wants {} but only {} is escrowed", wanted, reward);
+    }
+
+    open_epoch(config, epoch, index, reward, Some(&ctx.accounts.previous_epoch), clock.unix_timestamp);
+
+    if emission.crank_fee > 0 {
+        let bump = ctx.bumps.config;
+        let seeds = &[CONFIG.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.cranker_reward_account.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        transfer(cpi_ctx, emission.crank_fee)?;
+    }
+
+    msg!("⏭️ Advanced to epoch {} with reward {}", index, reward);
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
 
 use crate::{ state::*, constants::* , error::RichieError};
 
+use super::{ dual_boost::reapply_dual_boost, nft_boost::refresh_nft_boost, receipt::mint_receipt };
+
 #[derive(Accounts)]
 #[instruction(index: u64)]
 pub struct Stake<'info> {
@@ -53,16 +58,267 @@ pub struct Stake<'info> {
     )]
     pub stakes: Account<'info, Stakes>,
 
+    #[account(
+        seeds = [USER.as_bytes(), referrer_stake.owner.as_ref()],
+        bump
+    )]
+    pub referrer_stake: Option<Box<Account<'info, UserStake>>>, // optional referrer, must already have a position
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [REFERRAL.as_bytes(), user.key().as_ref()],
+        bump,
+        space = 8 + Referral::LEN
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>, // only with the stake that first links referrer_stake
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [RECEIPT.as_bytes(), user_stake.key().as_ref(), &user_stake.next_entry_id.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+    )]
+    pub receipt_mint: Option<Box<Account<'info, Mint>>>, // pass to issue a receipt NFT for the new entry
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user
+    )]
+    pub user_receipt_account: Option<Box<Account<'info, TokenAccount>>>, // required with receipt_mint
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct StakeFor<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: only used as the owner key of the stake position
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub stake_token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        seeds = [USER.as_bytes(), beneficiary.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    #[account(mut)]
+    pub from_token_account: Account<'info, TokenAccount>, // funder's $RICHIE
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Account<'info, Stakes>,
+
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Stakes `amount` as a new entry. With `receipt_mint` the entry is issued a receipt NFT,
+/// and withdraw and reward rights follow whoever holds it. The stake that first links a referrer
+/// passes the referrer's ancestor positions, nearest first, as remaining accounts.
 pub fn stake(ctx: Context<Stake>, index: u64, amount: u64, lock_period: u8) -> Result<()> {
+    let owner = ctx.accounts.user.key();
+    let receipt_mint = ctx.accounts.receipt_mint.as_ref().map_or(Pubkey::default(), |mint| mint.key());
+
+    let entry_id = record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.user_stake,
+        owner,
+        index,
+        amount,
+        lock_period,
+        receipt_mint,
+        Pubkey::default(),
+        ctx.accounts.boost_nft_account.as_ref(),
+    )?;
+
+    // Transfer tokens
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.from_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    if let Some(receipt_mint) = ctx.accounts.receipt_mint.as_ref() {
+        let receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(RichieError::InvalidReceipt)?;
+        mint_receipt(
+            &ctx.accounts.token_program,
+            &ctx.accounts.config,
+            ctx.bumps.config,
+            receipt_mint,
+            receipt_account,
+        )?;
+        msg!("🎟️ Minted receipt {} for entry {}", receipt_mint.key(), entry_id);
+    }
+
+    if let Some(referrer_stake) = ctx.accounts.referrer_stake.as_ref() {
+        register_referral(
+            &mut ctx.accounts.user_stake,
+            ctx.accounts.referral.as_deref_mut(),
+            referrer_stake,
+            ctx.remaining_accounts,
+            ctx.accounts.config.key(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Links the position to its referrer on the first referred stake; the referrer cannot change afterwards.
+/// `ancestors` are the positions above the referrer, walked up to Referral::MAX_DEPTH links.
+fn register_referral(
+    user_stake: &mut UserStake,
+    referral: Option<&mut Account<Referral>>,
+    referrer_stake: &UserStake,
+    ancestors: &[AccountInfo],
+    config_key: Pubkey,
+) -> Result<()> {
+    let referee = user_stake.owner;
+    let referrer = referrer_stake.owner;
+
+    if user_stake.referrer != Pubkey::default() {
+        require_keys_eq!(user_stake.referrer, referrer, RichieError::InvalidReferrer);
+        return Ok(());
+    }
+    let referral = referral.ok_or(RichieError::ReferralRequired)?;
+
+    require_keys_neq!(referrer, referee, RichieError::InvalidReferrer);
+    // The liquid pool position is owned by the config PDA
+    require_keys_neq!(referrer, config_key, RichieError::InvalidReferrer);
+
+    // The referee may already be somewhere above the referrer (A → B → C → A); walk the chain to its root
+    let mut ancestor = referrer_stake.referrer;
+    let mut chain = ancestors.iter();
+    for _ in 0..Referral::MAX_DEPTH {
+        if ancestor == Pubkey::default() {
+            break;
+        }
+        require_keys_neq!(ancestor, referee, RichieError::InvalidReferrer);
+
+        let info = chain.next().ok_or(RichieError::ReferralRequired)?;
+        let (expected, _) = Pubkey::find_program_address(&[USER.as_bytes(), ancestor.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected, RichieError::InvalidReferrer);
+        require_keys_eq!(*info.owner, crate::ID, RichieError::InvalidReferrer);
+        ancestor = UserStake::try_deserialize(&mut &info.try_borrow_data()?[..])?.referrer;
+    }
+    // Chains deeper than MAX_DEPTH cannot be checked within one transaction
+    require_keys_eq!(ancestor, Pubkey::default(), RichieError::InvalidReferrer);
+
+    user_stake.referrer = referrer;
+    referral.referee = referee;
+    referral.referrer = referrer;
+    referral.total_earned = 0;
+
+    msg!("🤝 {} referred by {}", referee, referrer);
+
+    Ok(())
+}
+
+pub fn stake_for(ctx: Context<StakeFor>, index: u64, amount: u64, lock_period: u8) -> Result<()> {
+    let beneficiary = ctx.accounts.beneficiary.key();
+    // The config PDA owns the liquid pool position, which only takes deposits through stake_liquid
+    require!(beneficiary != ctx.accounts.config.key(), RichieError::InvalidRecipient);
+    // Every stake_for opens an entry on someone else's position, so dust deposits could use up its MAX_ENTRIES slots
+    require!(amount >= ctx.accounts.config.stake_for_minimum, RichieError::BelowMinimumStake);
+
+    // The funder only pays; the position and its withdrawal rights belong to the beneficiary
+    let entry_id = record_stake(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.epoch,
+        &mut ctx.accounts.stakes,
+        &mut ctx.accounts.user_stake,
+        beneficiary,
+        index,
+        amount,
+        lock_period,
+        Pubkey::default(),
+        Pubkey::default(),
+        ctx.accounts.boost_nft_account.as_ref(),
+    )?;
+
+    // Transfer tokens
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.from_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    msg!("🎁 {} staked {} for {} as entry {}", ctx.accounts.funder.key(), amount, beneficiary, entry_id);
+
+    Ok(())
+}
+
+/// Validates the stake window and books `amount` as a stake entry of `owner`, returning the entry id.
+/// Pre-epoch stakes without a receipt are folded into the existing epoch-0 entry.
+/// `nft_mint` is set exactly when the pool stakes NFTs, each kept as its own entry.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_stake(
+    config: &mut Config,
+    epoch: &mut Epoch,
+    stakes: &mut Stakes,
+    user_stake: &mut Account<UserStake>,
+    owner: Pubkey,
+    index: u64,
+    amount: u64,
+    lock_period: u8,
+    receipt_mint: Pubkey,
+    nft_mint: Pubkey,
+    boost_nft_account: Option<&AccountInfo>,
+) -> Result<u64> {
     let clock = Clock::get()?;
-    let user_stake = &mut ctx.accounts.user_stake;
-    let config = &mut ctx.accounts.config;
-    let epoch = &mut ctx.accounts.epoch;
-    let stakes = &mut ctx.accounts.stakes;
+
+    require!(config.is_nft_pool() == (nft_mint != Pubkey::default()), RichieError::InvalidPoolType);
 
     if index == 0 {
         // Pre-epoch staking allowed any time with lock_period = 1
@@ -78,8 +334,14 @@ pub fn stake(ctx: Context<Stake>, index: u64, amount: u64, lock_period: u8) -> R
     }
     require!(index == config.index, RichieError::InvalidEpochIndex);
 
-    if user_stake.stake_entries.is_empty() {
-        user_stake.owner = ctx.accounts.user.key();
+    if user_stake.owner == Pubkey::default() {
+        user_stake.owner = owner;
+    }
+    // New entries carry the same NFT bonus as the rest of the position this epoch; it is taken
+    // back at settlement if the NFT has left the wallet
+    refresh_nft_boost(user_stake, boost_nft_account)?;
+    if !stakes.list.contains(&user_stake.key()) {
+        require!(stakes.list.len() < Stakes::MAX_USERS, RichieError::TooManyStakers);
         stakes.list.push(user_stake.key());
     }
 
@@ -90,32 +352,34 @@ pub fn stake(ctx: Context<Stake>, index: u64, amount: u64, lock_period: u8) -> R
         let available_time = epoch.stake_duration - (clock.unix_timestamp - epoch.staked_start_time);
         let multiplier = get_multiplier(config, lock_period)?;
         let base_curve = amount * available_time as u64;
-        let boosted_curve = base_curve * multiplier / 100;
+        let boosted_curve = StakeEntry::boost(base_curve, multiplier, user_stake.nft_boost_applied + user_stake.dual_boost);
         (base_curve, boosted_curve, multiplier)
     };
 
-    // Append new stake entry
-    if index == 0 {
-        if let Some(entry) = user_stake
+    let pre_stake_entry = if index == 0 && receipt_mint == Pubkey::default() && nft_mint == Pubkey::default() {
+        user_stake
             .stake_entries
             .iter_mut()
-            .find(|e| e.last_staked_epoch_index == 0)
-        {
-            entry.amount += amount;
-            // Optional: update base/boosted_curve if you want to accumulate (but likely 0 for epoch 0)
-        } else {
-            user_stake.stake_entries.push(StakeEntry {
-                amount,
-                last_staked_epoch_index: index,
-                lock_period,
-                multiplier,
-                base_curve,
-                boosted_curve,
-                calculated_index: 0,
-            });
-        }
+            // Unbonding entries have left the pool and NFT entries hold a single NFT, so neither takes top-ups
+            .find(|e| e.last_staked_epoch_index == 0 && !e.has_receipt() && !e.is_unbonding() && !e.is_nft())
+    } else {
+        None
+    };
+
+    // Append new stake entry
+    let lock_end = index + lock_period as u64;
+    let entry_id = if let Some(entry) = pre_stake_entry {
+        entry.amount += amount;
+        // Optional: update base/boosted_curve if you want to accumulate (but likely 0 for epoch 0)
+        entry.id
     } else {
+        require!(
+            user_stake.stake_entries.len() < UserStake::MAX_ENTRIES,
+            RichieError::TooManyEntries
+        );
+        let id = user_stake.allocate_entry_id();
         user_stake.stake_entries.push(StakeEntry {
+            id,
             amount,
             last_staked_epoch_index: index,
             lock_period,
@@ -123,31 +387,34 @@ pub fn stake(ctx: Context<Stake>, index: u64, amount: u64, lock_period: u8) -> R
             base_curve,
             boosted_curve,
             calculated_index: 0,
+            receipt_mint,
+            pending_reward: 0,
+            extra_pending_rewards: [0; Config::MAX_EXTRA_REWARDS],
+            unbond_release_time: 0,
+            unbond_penalty: 0,
+            nft_mint,
         });
-    }
+        id
+    };
 
     // Update epoch stats
     if index != 0 {
         epoch.total_curve += boosted_curve;
         epoch.total_staked_amount += amount;
     }
-    // Transfer tokens
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.from_token_account.to_account_info(),
-        to: ctx.accounts.stake_vault.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-    transfer(cpi_ctx, amount)?;
 
     config.total_staked += amount;
+    config.add_voting_lock(amount, lock_end);
 
-    Ok(())
+    // More principal dilutes the boost/main ratio
+    reapply_dual_boost(config, epoch, user_stake)?;
+
+    Ok(entry_id)
 }
 
-fn get_multiplier(config: &Config, lock_period: u8) -> Result<u64> {
+pub(crate) fn get_multiplier(config: &Config, lock_period: u8) -> Result<u64> {
     match lock_period {
-        1 => Ok(*config.multiplier.get(0).ok_or(RichieError::InvalidLockPeriod)?),
+        1 => Ok(*config.multiplier.first().ok_or(RichieError::InvalidLockPeriod)?),
         2 => Ok(*config.multiplier.get(1).ok_or(RichieError::InvalidLockPeriod)?),
         4 => Ok(*config.multiplier.get(2).ok_or(RichieError::InvalidLockPeriod)?),
         8 => Ok(*config.multiplier.get(3).ok_or(RichieError::InvalidLockPeriod)?),
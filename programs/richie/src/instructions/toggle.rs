@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
-use crate::{ state::*, constants::* , error::RichieError};
+use crate::{ state::*, constants::* , error::RichieError, events::EpochRewardAdded };
+
+use super::nft_boost::refresh_nft_boost;
 
 #[derive(Accounts)]
 #[instruction(index: u64)]
@@ -26,6 +28,12 @@ pub struct Toggle<'info> {
     )]
     pub epoch: Account<'info, Epoch>,
 
+    #[account(
+        seeds = [EPOCH.as_bytes(), &index.saturating_sub(1).to_le_bytes()],
+        bump
+    )]
+    pub previous_epoch: Option<Account<'info, Epoch>>, // required for every epoch after the pre-staking one
+
     #[account(mut)]
     pub reward_mint: Account<'info, Mint>,
 
@@ -81,11 +89,99 @@ pub struct ManageStakerReward<'info> {
     )]
     pub stakes: Account<'info, Stakes>,
 
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.referrer.as_ref()],
+        bump
+    )]
+    pub referrer_stake: Option<Box<Account<'info, UserStake>>>, // required when user_stake has a referrer
+
+    #[account(
+        mut,
+        seeds = [REFERRAL.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+
+    /// CHECK: required when user_stake has a boost NFT; must be user_stake.boost_nft_account, read in nft_boost_held
+    pub boost_nft_account: Option<AccountInfo<'info>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct AddEpochReward<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    #[account(
+        mut,
+        token::mint = config.reward_token_mint
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct AddExtraEpochReward<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint
+    )]
+    pub funder_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD.as_bytes(), reward_mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 pub fn toggle(ctx: Context<Toggle>, index: u64, reward_amount: u64) -> Result<()> {
     let clock = Clock::get()?;
     let config = &mut ctx.accounts.config;
@@ -93,25 +189,28 @@ pub fn toggle(ctx: Context<Toggle>, index: u64, reward_amount: u64) -> Result<()
     let owner = &mut ctx.accounts.owner;
 
     require!(owner.key() == config.admin, RichieError::UnAuthorized);
-    let mut duration = 0;
 
     if index == 0 {
         require!(reward_amount == 0, RichieError::InvalidRewardAmount);
         require!(index == config.index, RichieError::InvalidEpochIndex); // we use index 0 as staking before first epoch
-        duration = 6 * 60 * 60; // 6 hours
     } else {
-        require!(reward_amount > 0, RichieError::InvalidRewardAmount);
+        // A configured emission policy fixes the amount, possibly zero once it runs out; toggle must fund exactly that
+        match config.emission_policy.reward_for(index) {
+            Some(reward) => require!(reward_amount == reward, RichieError::InvalidRewardAmount),
+            None => require!(reward_amount > 0, RichieError::InvalidRewardAmount),
+        }
         require!(index == config.index + 1, RichieError::InvalidEpochIndex); // we use index 0 as staking before first epoch
-        duration = config.epoch_duration;
-        config.index += 1;
     }
 
-    epoch.index = index;
-    epoch.staked_start_time = clock.unix_timestamp;
-    epoch.stake_duration = duration;
-    epoch.staked_end_time = epoch.staked_start_time + duration;
+    let previous_epoch = if index == 0 {
+        None
+    } else {
+        Some(ctx.accounts.previous_epoch.as_deref().ok_or(RichieError::InvalidEpochIndex)?)
+    };
 
-    if index > 0 {
+    open_epoch(config, epoch, index, reward_amount, previous_epoch, clock.unix_timestamp);
+
+    if reward_amount > 0 {
         // Transfer tokens
         let cpi_accounts = Transfer {
             from: ctx.accounts.reward_mint_token_account.to_account_info(),
@@ -121,8 +220,43 @@ pub fn toggle(ctx: Context<Toggle>, index: u64, reward_amount: u64) -> Result<()
         let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
         transfer(cpi_ctx, reward_amount)?;
     }
+    
+    Ok(())
+}
 
-    epoch.reward = reward_amount;
+/// Starts epoch `index` with `reward` plus the previous epoch's unallocated remainder and any
+/// swept rewards, rolling the curves settled for it into the epoch totals.
+pub(crate) fn open_epoch(config: &mut Config, epoch: &mut Epoch, index: u64, reward: u64, previous: Option<&Epoch>, now: i64) {
+    let duration = if index == 0 {
+        6 * 60 * 60 // 6 hours
+    } else {
+        config.index += 1;
+        config.checkpoint_voting();
+        config.epoch_duration
+    };
+
+    epoch.index = index;
+    epoch.staked_start_time = now;
+    epoch.stake_duration = duration;
+    epoch.staked_end_time = epoch.staked_start_time + duration;
+
+    // The carried amount is already sitting in reward_vault
+    let carried_over = previous.map_or(0, |previous| previous.unallocated() + previous.referral_unpaid())
+        + std::mem::take(&mut config.swept_rewards);
+    // Carried rewards were charged when first funded
+    let (reward, protocol_fee) = config.take_protocol_fee(reward);
+    epoch.protocol_fee = protocol_fee;
+    epoch.reward = 0;
+    epoch.referral_reserve = 0;
+    epoch.referral_paid = 0;
+    epoch.fund(reward + carried_over, config.referral_bps);
+    epoch.carried_over = carried_over;
+    epoch.allocated = 0;
+    // Extra rewards are funded per epoch through add_extra_epoch_reward, on top of what is left over
+    for slot in 0..Config::MAX_EXTRA_REWARDS {
+        epoch.extra_rewards[slot] = previous.map_or(0, |previous| previous.extra_unallocated(slot));
+        epoch.extra_allocated[slot] = 0;
+    }
     epoch.total_staked_amount = config.total_staked;
     if index == 1 {
         epoch.total_curve = epoch.total_staked_amount * epoch.stake_duration as u64;
@@ -131,7 +265,87 @@ pub fn toggle(ctx: Context<Toggle>, index: u64, reward_amount: u64) -> Result<()
     }
 
     config.total_curve = 0;
-    
+    config.last_epoch_time = now;
+}
+
+pub fn add_epoch_reward(ctx: Context<AddEpochReward>, index: u64, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let funder = &ctx.accounts.funder;
+
+    require!(config.can_fund_rewards(funder.key()), RichieError::UnAuthorized);
+    require!(amount > 0, RichieError::InvalidRewardAmount);
+    require!(index > 0 && index == config.index, RichieError::InvalidEpochIndex);
+    // Shares are computed against epoch.reward once settlement begins
+    require!(
+        clock.unix_timestamp <= epoch.staked_end_time && !epoch.claimable,
+        RichieError::EpochRewardLocked
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: funder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    let (reward, protocol_fee) = config.take_protocol_fee(amount);
+    epoch.fund(reward, config.referral_bps);
+    epoch.protocol_fee += protocol_fee;
+
+    emit!(EpochRewardAdded {
+        epoch_index: index,
+        reward_mint: config.reward_token_mint,
+        contributor: funder.key(),
+        amount,
+        total_reward: epoch.reward,
+    });
+
+    Ok(())
+}
+
+/// Same as add_epoch_reward for one of the pool's extra reward mints.
+pub fn add_extra_epoch_reward(ctx: Context<AddExtraEpochReward>, index: u64, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let funder = &ctx.accounts.funder;
+    let reward_mint = ctx.accounts.reward_mint.key();
+
+    let slot = config
+        .extra_reward_mints
+        .iter()
+        .position(|mint| *mint == reward_mint)
+        .ok_or(RichieError::UnknownRewardMint)?;
+
+    require!(config.can_fund_rewards(funder.key()), RichieError::UnAuthorized);
+    require!(amount > 0, RichieError::InvalidRewardAmount);
+    require!(index > 0 && index == config.index, RichieError::InvalidEpochIndex);
+    require!(
+        clock.unix_timestamp <= epoch.staked_end_time && !epoch.claimable,
+        RichieError::EpochRewardLocked
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        to: ctx.accounts.extra_reward_vault.to_account_info(),
+        authority: funder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    epoch.extra_rewards[slot] += amount;
+
+    emit!(EpochRewardAdded {
+        epoch_index: index,
+        reward_mint,
+        contributor: funder.key(),
+        amount,
+        total_reward: epoch.extra_rewards[slot],
+    });
+
     Ok(())
 }
 
@@ -151,44 +365,84 @@ pub fn manage_staker_reward(ctx: Context<ManageStakerReward>, index: u64) -> Res
         epoch.staked_start_time + epoch.stake_duration < clock.unix_timestamp,
         RichieError::UnFinishedEpoch
     );
+    // Merkle epochs are claimed by proof and need no settlement; curves catch up below instead
+    require!(!epoch.is_merkle(), RichieError::EpochAlreadySettled);
 
     let duration = config.epoch_duration;
 
+    // Entries that skipped merkle epochs earn as if those had been settled
+    user_stake.catch_up(index, epoch.stake_duration, config);
+
+    // Epochs only count towards the streak while something is staked
+    if index != 0 && user_stake.stake_entries.iter().any(|e| !e.is_unbonding()) {
+        user_stake.extend_streak(index);
+    }
+
+    // The NFT boost lasts only while the registered account still holds the NFT. Curves carry
+    // nft_boost_applied for the whole epoch, so a position that lost its NFT is paid without it.
+    refresh_nft_boost(user_stake, ctx.accounts.boost_nft_account.as_ref())?;
+    let lost_nft_boost = if user_stake.boost_nft_mint == Pubkey::default() { user_stake.nft_boost_applied } else { 0 };
+    user_stake.nft_boost_applied = user_stake.nft_boost(config);
+    user_stake.dual_boost = config.dual_boost(user_stake.boost_staked, user_stake.staked_amount());
+    let bonus = config.loyalty_bonus(user_stake.loyalty_streak) + user_stake.nft_boost(config) + user_stake.dual_boost;
+
+    // The liquid pool compounds only the primary reward and has no one to claim extras for it
+    let earns_extra = user_stake.owner != config.key();
+
     let mut reward_sum: u64 = 0;
+    let mut extra_reward_sums = [0u64; Config::MAX_EXTRA_REWARDS];
     for entry in user_stake.stake_entries.iter_mut() {
+        if entry.is_unbonding() {
+            // Stopped earning at request_unstake; marked so cancel_unstake rejoins next epoch's curve
+            entry.calculated_index = index;
+            continue;
+        }
         if entry.calculated_index == index {
             msg!("It was already calculated")
         } else {
-            let mut last_staked_epoch_index = 0;
-            
-            if entry.last_staked_epoch_index == 0 {
-                last_staked_epoch_index = 1;
+            // Locked entries earn with their lock multiplier and bonus, the rest at base weight
+            let earning_curve = if entry.is_locked_at(index) {
+                entry.boosted_curve - (entry.base_curve * lost_nft_boost / 100).min(entry.boosted_curve)
             } else {
-                last_staked_epoch_index = entry.last_staked_epoch_index;
-            }
-
-            if last_staked_epoch_index + entry.lock_period as u64 - 1 >= index {
-                let reward_share = (entry.boosted_curve as u128)
-                    .checked_mul(epoch.reward as u128)
+                entry.base_curve
+            };
+            let reward_share = (earning_curve as u128)
+                .checked_mul(epoch.reward as u128)
+                .unwrap_or(0)
+                .checked_div(epoch.total_curve as u128)
+                .unwrap_or(0) as u64;
+            entry.roll_forward(index, duration, bonus);
+
+            // Shares are floored and the denominator can shrink after early exits, so never hand out more than the epoch holds
+            let reward_share = reward_share.min(epoch.unallocated());
+            epoch.allocated += reward_share;
+
+            // The liquid pool only earns the primary reward, its extra share rolls into the next epoch
+            let extra_slots = if earns_extra { config.extra_reward_mints.len() } else { 0 };
+            let mut extra_shares = [0u64; Config::MAX_EXTRA_REWARDS];
+            for (slot, extra_share) in extra_shares.iter_mut().enumerate().take(extra_slots) {
+                let share = (earning_curve as u128)
+                    .checked_mul(epoch.extra_rewards[slot] as u128)
                     .unwrap_or(0)
                     .checked_div(epoch.total_curve as u128)
                     .unwrap_or(0) as u64;
+                *extra_share = share.min(epoch.extra_unallocated(slot));
+                epoch.extra_allocated[slot] += *extra_share;
+            }
 
-                reward_sum += reward_share;
-
-                entry.base_curve = entry.amount * duration as u64;
-                entry.boosted_curve = entry.base_curve * entry.multiplier;
+            // Receipt entries accrue to whoever holds the receipt, not to the account owner
+            if entry.has_receipt() {
+                if index != 0 {
+                    entry.pending_reward = entry.pending_reward.saturating_add(reward_share);
+                    for (pending, extra_share) in entry.extra_pending_rewards.iter_mut().zip(extra_shares) {
+                        *pending = pending.saturating_add(extra_share);
+                    }
+                }
             } else {
-                let reward_share = (entry.base_curve as u128)
-                    .checked_mul(epoch.reward as u128)
-                    .unwrap_or(0)
-                    .checked_div(epoch.total_curve as u128)
-                    .unwrap_or(0) as u64;
-
                 reward_sum += reward_share;
-
-                entry.base_curve = entry.amount * duration as u64;
-                entry.boosted_curve = entry.base_curve;
+                for (extra_sum, extra_share) in extra_reward_sums.iter_mut().zip(extra_shares) {
+                    *extra_sum += extra_share;
+                }
             }
             config.total_curve += entry.boosted_curve;
             entry.calculated_index = index;
@@ -198,7 +452,23 @@ pub fn manage_staker_reward(ctx: Context<ManageStakerReward>, index: u64) -> Res
     let is_epoch_zero = epoch.index == 0;
 
     if !is_epoch_zero {
-        user_stake.pending_reward = user_stake.pending_reward.saturating_add(reward_sum);
+        user_stake.credit_reward(index, reward_sum);
+        for (pending, extra_sum) in user_stake.extra_pending_rewards.iter_mut().zip(extra_reward_sums) {
+            *pending = pending.saturating_add(extra_sum);
+        }
+
+        // The referrer's bonus comes out of the epoch's referral reserve, not the referee's share
+        // or the reward still owed to stakers settled after this one
+        if user_stake.referrer != Pubkey::default() && config.referral_bps > 0 {
+            let referrer_stake = ctx.accounts.referrer_stake.as_mut().ok_or(RichieError::ReferralRequired)?;
+            let referral = ctx.accounts.referral.as_mut().ok_or(RichieError::ReferralRequired)?;
+
+            let bonus = (reward_sum as u128 * config.referral_bps as u128 / 10_000) as u64;
+            let bonus = bonus.min(epoch.referral_unpaid());
+            epoch.referral_paid += bonus;
+            referrer_stake.credit_reward(index, bonus);
+            referral.total_earned += bonus;
+        }
         if !epoch.claimable {
             epoch.claimable = true;
         }
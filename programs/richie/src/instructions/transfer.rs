@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::dual_boost::{ reapply_dual_boost, reweight_entry };
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    /// CHECK: only used as the owner key of the recipient's stake account
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [USER.as_bytes(), recipient.key().as_ref()],
+        bump,
+        space = 8 + UserStake::LEN
+    )]
+    pub recipient_stake: Box<Account<'info, UserStake>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE.as_bytes()],
+        bump
+    )]
+    pub stakes: Account<'info, Stakes>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn transfer_position(ctx: Context<TransferPosition>, entry_ids: Vec<u64>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let recipient_stake = &mut ctx.accounts.recipient_stake;
+    let stakes = &mut ctx.accounts.stakes;
+    let recipient = ctx.accounts.recipient.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(recipient != ctx.accounts.user.key(), RichieError::InvalidRecipient);
+    require!(recipient != config.key(), RichieError::InvalidRecipient);
+    require!(now >= user_stake.vote_locked_until, RichieError::VoteLocked);
+    require!(!entry_ids.is_empty(), RichieError::EntryNotFound);
+    require!(
+        recipient_stake.stake_entries.len() + entry_ids.len() <= UserStake::MAX_ENTRIES,
+        RichieError::TooManyEntries
+    );
+
+    if recipient_stake.owner == Pubkey::default() {
+        recipient_stake.owner = recipient;
+    }
+    if !stakes.list.contains(&recipient_stake.key()) {
+        require!(stakes.list.len() < Stakes::MAX_USERS, RichieError::TooManyStakers);
+        stakes.list.push(recipient_stake.key());
+    }
+
+    // Moved entries trade the sender's loyalty, NFT and dual bonus for the recipient's
+    let sender_bonus = user_stake.applied_bonus(config);
+    let recipient_bonus = recipient_stake.applied_bonus(config);
+
+    for id in entry_ids.iter() {
+        // Lock, multiplier, curves and settlement index travel with the entry;
+        // only the id is reissued from the recipient's counter.
+        let mut entry = user_stake.remove_entry(*id)?;
+        require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+        require!(!entry.is_unbonding(), RichieError::EntryUnbonding);
+        entry.id = recipient_stake.allocate_entry_id();
+        reweight_entry(config, epoch, &mut entry, sender_bonus, recipient_bonus, now);
+
+        msg!("📦 Moved entry {} to {} as entry {}", id, recipient, entry.id);
+        recipient_stake.stake_entries.push(entry);
+    }
+
+    // Both positions changed size, so both boost/main ratios moved
+    reapply_dual_boost(config, epoch, user_stake)?;
+    reapply_dual_boost(config, epoch, recipient_stake)?;
+
+    Ok(())
+}
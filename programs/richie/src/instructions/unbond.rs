@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::{ state::*, constants::* , error::RichieError };
+
+use super::{ claim::{ pay_out_principal, release_entry }, dual_boost::reapply_dual_boost, nft_pool::nft_exit_penalty };
+
+#[derive(Accounts)]
+pub struct Unbond<'info> {
+    pub authority: Signer<'info>, // owner, or receipt holder for receipt entries
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [EPOCH.as_bytes(), &config.index.to_le_bytes()],
+        bump
+    )]
+    pub epoch: Box<Account<'info, Epoch>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    pub receipt_account: Option<Box<Account<'info, TokenAccount>>>, // only for receipt entries
+}
+
+#[derive(Accounts)]
+pub struct CompleteUnstake<'info> {
+    pub authority: Signer<'info>, // owner, or receipt holder for receipt entries
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [USER.as_bytes(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    #[account(mut)]
+    pub stake_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT.as_bytes()],
+        bump,
+        token::mint = stake_token_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub to_token_account: Box<Account<'info, TokenAccount>>, // $RICHIE destination
+
+    #[account(mut)]
+    pub receipt_mint: Option<Box<Account<'info, Mint>>>, // only for receipt entries
+
+    #[account(mut)]
+    pub receipt_account: Option<Box<Account<'info, TokenAccount>>>, // only for receipt entries
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn request_unstake(ctx: Context<Unbond>, entry_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    // Without a cooldown entries are withdrawn directly; a zero-length unbonding would only skip the penalty checks there
+    require!(config.unbonding_period > 0, RichieError::InvalidUnbondingPeriod);
+
+    let entry = user_stake.entry(entry_id)?.clone();
+    authorize_entry(user_stake, &entry, ctx.accounts.authority.key(), ctx.accounts.receipt_account.as_deref())?;
+    // The entry leaves the pool now; only the payout waits for the cooldown
+    let (payout, penalty) = release_entry(config, epoch, user_stake, &entry)?;
+    // NFTs have no amount to cut and pay the reward-token penalty in withdraw_nft instead
+    let penalty = if entry.is_nft() { nft_exit_penalty(config, &entry) } else { penalty };
+
+    let entry = user_stake.entry_mut(entry_id)?;
+    entry.base_curve = 0;
+    entry.boosted_curve = 0;
+    entry.unbond_penalty = penalty;
+    entry.unbond_release_time = clock.unix_timestamp + config.unbonding_period;
+
+    msg!("⏳ Entry {} unbonding, {} claimable at {}", entry_id, payout, entry.unbond_release_time);
+
+    reapply_dual_boost(config, epoch, user_stake)?;
+
+    Ok(())
+}
+
+pub fn cancel_unstake(ctx: Context<Unbond>, entry_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let epoch = &mut ctx.accounts.epoch;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    let entry = user_stake.entry(entry_id)?.clone();
+    require!(entry.is_unbonding(), RichieError::EntryNotUnbonding);
+    authorize_entry(user_stake, &entry, ctx.accounts.authority.key(), ctx.accounts.receipt_account.as_deref())?;
+
+    // Settlement pays the loyalty bonus from the next epoch on, mid-epoch stakes do not carry it
+    let settled_bonus = user_stake.applied_bonus(config);
+    let running_bonus = user_stake.nft_boost_applied + user_stake.dual_boost;
+    let entry = user_stake.entry_mut(entry_id)?;
+    if entry.calculated_index == config.index && config.index != 0 {
+        // The position was settled while this entry unbonded: it rejoins next epoch's curve in full
+        entry.roll_forward(config.index, config.epoch_duration, settled_bonus);
+        config.total_curve += entry.boosted_curve;
+    } else {
+        // Rejoin the pool like a fresh stake: the entry earns only for the rest of this epoch
+        let (base_curve, boosted_curve) = if config.index == 0 {
+            (0, 0)
+        } else {
+            let available_time = (epoch.staked_end_time - clock.unix_timestamp).max(0);
+            let base_curve = entry.amount * available_time as u64;
+            let boosted_curve = if entry.lock_end() > config.index {
+                StakeEntry::boost(base_curve, entry.multiplier, running_bonus)
+            } else {
+                base_curve
+            };
+            (base_curve, boosted_curve)
+        };
+        epoch.total_curve += boosted_curve;
+        entry.base_curve = base_curve;
+        entry.boosted_curve = boosted_curve;
+        entry.calculated_index = 0;
+    }
+    entry.unbond_penalty = 0;
+    entry.unbond_release_time = 0;
+
+    config.total_staked += entry.amount;
+    config.add_voting_lock(entry.amount, entry.lock_end());
+
+    msg!("↩️ Entry {} unbonding cancelled", entry_id);
+
+    reapply_dual_boost(config, epoch, user_stake)?;
+
+    Ok(())
+}
+
+pub fn complete_unstake(ctx: Context<CompleteUnstake>, entry_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    let entry = user_stake.entry(entry_id)?.clone();
+    require!(entry.is_unbonding(), RichieError::EntryNotUnbonding);
+    require!(clock.unix_timestamp >= entry.unbond_release_time, RichieError::UnbondingNotFinished);
+    authorize_entry(user_stake, &entry, ctx.accounts.authority.key(), ctx.accounts.receipt_account.as_deref())?;
+    // Unbonded NFTs come back through withdraw_nft
+    require!(!entry.is_nft(), RichieError::NftEntry);
+    // Receipt rewards live on the entry, so they must be claimed before it goes away
+    require!(!entry.has_pending_rewards(), RichieError::ReceiptRewardPending);
+
+    user_stake.remove_entry(entry_id)?;
+    let payout = entry.amount - entry.unbond_penalty;
+
+    pay_out_principal(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.config,
+        ctx.bumps.config,
+        &ctx.accounts.stake_token_mint,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.to_token_account,
+        payout,
+        entry.unbond_penalty,
+    )?;
+
+    if entry.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(RichieError::InvalidReceipt)?;
+        let receipt_account = ctx.accounts.receipt_account.as_ref().ok_or(RichieError::InvalidReceipt)?;
+        require_keys_eq!(receipt_mint.key(), entry.receipt_mint, RichieError::InvalidReceipt);
+
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: receipt_mint.to_account_info(),
+                from: receipt_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        );
+        burn(burn_ctx, 1)?;
+    }
+
+    msg!("✅ Entry {} unbonded, paid out {}", entry_id, payout);
+
+    Ok(())
+}
+
+/// Receipt entries answer to the receipt holder, all others to the position owner.
+fn authorize_entry(
+    user_stake: &UserStake,
+    entry: &StakeEntry,
+    authority: Pubkey,
+    receipt_account: Option<&Account<TokenAccount>>,
+) -> Result<()> {
+    if entry.has_receipt() {
+        let receipt_account = receipt_account.ok_or(RichieError::InvalidReceipt)?;
+        require!(
+            receipt_account.mint == entry.receipt_mint
+                && receipt_account.owner == authority
+                && receipt_account.amount == 1,
+            RichieError::InvalidReceipt
+        );
+    } else {
+        require_keys_eq!(authority, user_stake.owner, RichieError::UnAuthorized);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{ state::*, constants::* };
+
+#[derive(Accounts)]
+pub struct ViewVotingPower<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub user_stake: Account<'info, UserStake>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VotingPower {
+    pub user: u64,
+    pub total: u64,
+    pub index: u64,
+}
+
+/// Returned through set_return_data so other programs can read it after a CPI.
+pub fn get_voting_power(ctx: Context<ViewVotingPower>) -> Result<VotingPower> {
+    let config = &ctx.accounts.config;
+
+    Ok(VotingPower {
+        user: ctx.accounts.user_stake.voting_power(config.index),
+        total: config.total_voting_power(),
+        index: config.index,
+    })
+}
@@ -1,5 +1,7 @@
 pub mod constants;
 pub mod error;
+pub mod events;
+pub mod merkle;
 pub mod instructions;
 pub mod state;
 
@@ -29,6 +31,12 @@ pub mod richie {
         instructions::initialize_reward_vault(ctx)
     }
 
+    pub fn add_reward_mint(
+        ctx: Context<AddRewardMint>,
+    ) -> Result<()> {
+        instructions::add_reward_mint(ctx)
+    }
+
     pub fn update_epoch_duration(
         ctx: Context<ManageConfig>,
         duration: i64
@@ -36,6 +44,99 @@ pub mod richie {
         instructions::update_epoch_duration(ctx, duration)
     }
 
+    pub fn update_unbonding_period(
+        ctx: Context<ManageConfig>,
+        period: i64
+    ) -> Result<()> {
+        instructions::update_unbonding_period(ctx, period)
+    }
+
+    pub fn update_governance(
+        ctx: Context<ManageConfig>,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+        vote_lock: i64
+    ) -> Result<()> {
+        instructions::update_governance(ctx, realm, governing_token_mint, vote_lock)
+    }
+
+    pub fn update_emission_policy(
+        ctx: Context<ManageConfig>,
+        policy: EmissionPolicy
+    ) -> Result<()> {
+        instructions::update_emission_policy(ctx, policy)
+    }
+
+    pub fn update_reward_funders(
+        ctx: Context<ManageConfig>,
+        funders: Vec<Pubkey>
+    ) -> Result<()> {
+        instructions::update_reward_funders(ctx, funders)
+    }
+
+    pub fn update_claim_expiry(
+        ctx: Context<ManageConfig>,
+        expiry_epochs: u64,
+        treasury: Pubkey
+    ) -> Result<()> {
+        instructions::update_claim_expiry(ctx, expiry_epochs, treasury)
+    }
+
+    pub fn update_protocol_fee(
+        ctx: Context<ManageConfig>,
+        fee_bps: u16,
+        treasurer: Pubkey,
+        treasury: Pubkey
+    ) -> Result<()> {
+        instructions::update_protocol_fee(ctx, fee_bps, treasurer, treasury)
+    }
+
+    pub fn update_referral_bps(
+        ctx: Context<ManageConfig>,
+        referral_bps: u16
+    ) -> Result<()> {
+        instructions::update_referral_bps(ctx, referral_bps)
+    }
+
+    pub fn update_loyalty_tiers(
+        ctx: Context<ManageConfig>,
+        tiers: Vec<LoyaltyTier>
+    ) -> Result<()> {
+        instructions::update_loyalty_tiers(ctx, tiers)
+    }
+
+    pub fn update_nft_boost(
+        ctx: Context<ManageConfig>,
+        boost: u64,
+        allowlist: Vec<Pubkey>
+    ) -> Result<()> {
+        instructions::update_nft_boost(ctx, boost, allowlist)
+    }
+
+    pub fn update_dual_boost(
+        ctx: Context<ManageConfig>,
+        max_bonus: u64,
+        ratio_bps: u64
+    ) -> Result<()> {
+        instructions::update_dual_boost(ctx, max_bonus, ratio_bps)
+    }
+
+    pub fn update_stake_for_minimum(
+        ctx: Context<ManageConfig>,
+        minimum: u64
+    ) -> Result<()> {
+        instructions::update_stake_for_minimum(ctx, minimum)
+    }
+
+    pub fn update_nft_pool(
+        ctx: Context<ManageConfig>,
+        collection: Pubkey,
+        weight: u64,
+        penalty: u64
+    ) -> Result<()> {
+        instructions::update_nft_pool(ctx, collection, weight, penalty)
+    }
+
     pub fn update_multiplier(
         ctx: Context<ManageConfig>,
         new_multiplier: Vec<u64>
@@ -51,6 +152,34 @@ pub mod richie {
         instructions::toggle(ctx, index, reward_amount)
     }
 
+    pub fn fund_schedule(
+        ctx: Context<FundSchedule>,
+        rewards: Vec<u64>,
+        crank_fee: u64
+    ) -> Result<()> {
+        instructions::fund_schedule(ctx, rewards, crank_fee)
+    }
+
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        instructions::advance_epoch(ctx)
+    }
+
+    pub fn add_epoch_reward(
+        ctx: Context<AddEpochReward>,
+        index: u64,
+        amount: u64
+    ) -> Result<()> {
+        instructions::add_epoch_reward(ctx, index, amount)
+    }
+
+    pub fn add_extra_epoch_reward(
+        ctx: Context<AddExtraEpochReward>,
+        index: u64,
+        amount: u64
+    ) -> Result<()> {
+        instructions::add_extra_epoch_reward(ctx, index, amount)
+    }
+
     pub fn manage_staker_reward(
         ctx: Context<ManageStakerReward>,
         index: u64
@@ -58,6 +187,38 @@ pub mod richie {
         instructions::manage_staker_reward(ctx, index)
     }
 
+    pub fn publish_merkle_root(
+        ctx: Context<PublishMerkleRoot>,
+        index: u64,
+        merkle_root: [u8; 32],
+        total: u64,
+        next_total_curve: u64
+    ) -> Result<()> {
+        instructions::publish_merkle_root(ctx, index, merkle_root, total, next_total_curve)
+    }
+
+    pub fn claim_merkle_reward(
+        ctx: Context<ClaimMerkleReward>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        instructions::claim_merkle_reward(ctx, index, amount, proof)
+    }
+
+    pub fn release_unclaimed_merkle(
+        ctx: Context<ReleaseUnclaimedMerkle>,
+        index: u64
+    ) -> Result<()> {
+        instructions::release_unclaimed_merkle(ctx, index)
+    }
+
+    pub fn sweep_expired_rewards(
+        ctx: Context<SweepExpiredRewards>
+    ) -> Result<()> {
+        instructions::sweep_expired_rewards(ctx)
+    }
+
     pub fn stake(
         ctx: Context<Stake>,
         index: u64,
@@ -67,14 +228,207 @@ pub mod richie {
         instructions::stake(ctx, index, amount, lock_period)
     }
 
-    pub fn claim(
-        ctx: Context<Claim>
+    pub fn stake_for(
+        ctx: Context<StakeFor>,
+        index: u64,
+        amount: u64,
+        lock_period: u8
+    ) -> Result<()> {
+        instructions::stake_for(ctx, index, amount, lock_period)
+    }
+
+    pub fn claim_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReceipt<'info>>,
+        entry_id: u64,
+        reward_mask: u8
+    ) -> Result<()> {
+        instructions::claim_receipt(ctx, entry_id, reward_mask)
+    }
+
+    pub fn withdraw_receipt<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawReceipt<'info>>, entry_id: u64) -> Result<()> {
+        instructions::withdraw_receipt(ctx, entry_id)
+    }
+
+    pub fn initialize_liquid(ctx: Context<InitializeLiquid>) -> Result<()> {
+        instructions::initialize_liquid(ctx)
+    }
+
+    pub fn stake_liquid(ctx: Context<StakeLiquid>, index: u64, amount: u64) -> Result<()> {
+        instructions::stake_liquid(ctx, index, amount)
+    }
+
+    pub fn redeem_liquid(ctx: Context<RedeemLiquid>, shares: u64) -> Result<()> {
+        instructions::redeem_liquid(ctx, shares)
+    }
+
+    pub fn compound_liquid(ctx: Context<CompoundLiquid>, index: u64) -> Result<()> {
+        instructions::compound_liquid(ctx, index)
+    }
+
+    pub fn claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, Claim<'info>>,
+        reward_mask: u8
+    ) -> Result<()> {
+        instructions::claim(ctx, reward_mask)
+    }
+
+    pub fn compound(ctx: Context<Compound>, index: u64, lock_period: u8) -> Result<()> {
+        instructions::compound(ctx, index, lock_period)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, entry_id: u64) -> Result<()> {
+        instructions::withdraw(ctx, entry_id)
+    }
+
+    pub fn set_operator(
+        ctx: Context<ManagePosition>,
+        operator: Pubkey,
+        permissions: u8
+    ) -> Result<()> {
+        instructions::set_operator(ctx, operator, permissions)
+    }
+
+    pub fn revoke_operator(ctx: Context<ManagePosition>) -> Result<()> {
+        instructions::revoke_operator(ctx)
+    }
+
+    pub fn extend_lock(
+        ctx: Context<OperatePosition>,
+        entry_id: u64,
+        lock_period: u8
+    ) -> Result<()> {
+        instructions::extend_lock(ctx, entry_id, lock_period)
+    }
+
+    pub fn merge_entries(
+        ctx: Context<ManagePosition>,
+        entry_ids: Vec<u64>
+    ) -> Result<()> {
+        instructions::merge_entries(ctx, entry_ids)
+    }
+
+    pub fn transfer_position(
+        ctx: Context<TransferPosition>,
+        entry_ids: Vec<u64>
+    ) -> Result<()> {
+        instructions::transfer_position(ctx, entry_ids)
+    }
+
+    pub fn register_nft_boost(
+        ctx: Context<RegisterNftBoost>
+    ) -> Result<()> {
+        instructions::register_nft_boost(ctx)
+    }
+
+    pub fn unregister_nft_boost(
+        ctx: Context<ManagePosition>
     ) -> Result<()> {
-        instructions::claim(ctx)
+        instructions::unregister_nft_boost(ctx)
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, index: u64) -> Result<()> {
-        instructions::withdraw(ctx, index)
+    pub fn initialize_boost_vault(
+        ctx: Context<InitializeBoostVault>
+    ) -> Result<()> {
+        instructions::initialize_boost_vault(ctx)
+    }
+
+    pub fn stake_boost(
+        ctx: Context<ManageBoostStake>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::stake_boost(ctx, amount)
+    }
+
+    pub fn unstake_boost(
+        ctx: Context<ManageBoostStake>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::unstake_boost(ctx, amount)
+    }
+
+    pub fn stake_nft(
+        ctx: Context<StakeNft>,
+        index: u64,
+        lock_period: u8
+    ) -> Result<()> {
+        instructions::stake_nft(ctx, index, lock_period)
+    }
+
+    pub fn withdraw_nft(
+        ctx: Context<WithdrawNft>,
+        entry_id: u64
+    ) -> Result<()> {
+        instructions::withdraw_nft(ctx, entry_id)
+    }
+
+    pub fn stake_sol(
+        ctx: Context<StakeSol>,
+        index: u64,
+        amount: u64,
+        lock_period: u8
+    ) -> Result<()> {
+        instructions::stake_sol(ctx, index, amount, lock_period)
+    }
+
+    pub fn withdraw_sol(
+        ctx: Context<WithdrawSol>,
+        entry_id: u64
+    ) -> Result<()> {
+        instructions::withdraw_sol(ctx, entry_id)
+    }
+
+    pub fn complete_unstake_sol(
+        ctx: Context<CompleteUnstakeSol>,
+        entry_id: u64
+    ) -> Result<()> {
+        instructions::complete_unstake_sol(ctx, entry_id)
+    }
+
+    pub fn claim_sol(
+        ctx: Context<ClaimSol>
+    ) -> Result<()> {
+        instructions::claim_sol(ctx)
+    }
+
+    pub fn withdraw_native_penalties(
+        ctx: Context<WithdrawNativePenalties>
+    ) -> Result<()> {
+        instructions::withdraw_native_penalties(ctx)
+    }
+
+    pub fn get_stake_entry(
+        ctx: Context<ViewStakeEntry>,
+        entry_id: u64
+    ) -> Result<StakeEntry> {
+        instructions::get_stake_entry(ctx, entry_id)
+    }
+
+    pub fn withdraw_and_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawAndClaim<'info>>,
+        entry_id: u64,
+        reward_mask: u8
+    ) -> Result<()> {
+        instructions::withdraw_and_claim(ctx, entry_id, reward_mask)
+    }
+
+    pub fn request_unstake(ctx: Context<Unbond>, entry_id: u64) -> Result<()> {
+        instructions::request_unstake(ctx, entry_id)
+    }
+
+    pub fn cancel_unstake(ctx: Context<Unbond>, entry_id: u64) -> Result<()> {
+        instructions::cancel_unstake(ctx, entry_id)
+    }
+
+    pub fn complete_unstake(ctx: Context<CompleteUnstake>, entry_id: u64) -> Result<()> {
+        instructions::complete_unstake(ctx, entry_id)
+    }
+
+    pub fn get_voting_power(ctx: Context<ViewVotingPower>) -> Result<VotingPower> {
+        instructions::get_voting_power(ctx)
+    }
+
+    pub fn update_voter_weight_record(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+        instructions::update_voter_weight_record(ctx)
     }
 
     pub fn admin_withdraw_stake_mint(ctx: Context<AdminStakeTokenWithdraw>, amount: u64) -> Result<()> {
@@ -84,4 +438,8 @@ pub mod richie {
     pub fn admin_withdraw_reward_mint(ctx: Context<AdminRewardTokenWithdraw>, amount: u64) -> Result<()> {
         instructions::admin_withdraw_reward_mint(ctx, amount)
     }
+
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+        instructions::withdraw_protocol_fees(ctx)
+    }
 }
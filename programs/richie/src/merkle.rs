@@ -0,0 +1,227 @@
+//! Merkle tree over per-epoch `(owner, amount)` payouts.
+//!
+//! Leaves are `keccak(0x00 || epoch_index || owner || amount)` and inner nodes hash their two
+//! children in sorted order behind a `0x01` prefix, so proofs carry no left/right flags and a
+//! leaf can never be passed off as an inner node. The program only uses `leaf` and `verify`;
+//! the rest builds roots, proofs and the next epoch's total curve off-chain from account snapshots.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+use crate::state::{ Config, Epoch, StakeEntry, UserStake };
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(epoch_index: u64, owner: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &epoch_index.to_le_bytes(), owner.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == *root
+}
+
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>, // layers[0] are the leaves, the last layer holds the root
+}
+
+impl MerkleTree {
+    /// Builds the tree bottom-up; an odd node out is carried to the next layer unchanged.
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// Builds the tree for an epoch's payouts, in the order given.
+    pub fn from_payouts(epoch_index: u64, payouts: &[(Pubkey, u64)]) -> Self {
+        Self::new(payouts.iter().map(|(owner, amount)| leaf(epoch_index, owner, *amount)).collect())
+    }
+
+    /// Root to publish, all zeroes for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().and_then(|layer| layer.first()).copied().unwrap_or_default()
+    }
+
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = vec![];
+        for layer in self.layers.iter().take(self.layers.len().saturating_sub(1)) {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+/// Curve an entry earns over `epoch`, following the same lock and bonus rules as manage_staker_reward.
+/// `bonus` is the position's loyalty, NFT and dual-token boost.
+/// Receipt entries pay their holder rather than the owner and unbonding entries no longer earn,
+/// so both are left out; whatever they would have earned rolls into the next epoch.
+pub fn entry_curve(entry: &StakeEntry, epoch: &Epoch, bonus: u64) -> u64 {
+    if entry.has_receipt() || entry.is_unbonding() || entry.last_staked_epoch_index > epoch.index {
+        return 0;
+    }
+    if entry.last_staked_epoch_index == epoch.index {
+        // Staked mid-epoch: the curve recorded at stake time covers the time left in the epoch
+        return entry.boosted_curve;
+    }
+
+    let start = entry.last_staked_epoch_index.max(1);
+    let base_curve = entry.amount * epoch.stake_duration as u64;
+    if start + entry.lock_period as u64 > epoch.index {
+        StakeEntry::boost(base_curve, entry.multiplier, bonus)
+    } else {
+        base_curve
+    }
+}
+
+/// Splits `epoch.reward` between positions pro rata to their curves, rounding down.
+/// Positions earning nothing are left out.
+pub fn epoch_payouts(config: &Config, epoch: &Epoch, positions: &[UserStake]) -> Vec<(Pubkey, u64)> {
+    let curves: Vec<(Pubkey, u128)> = positions
+        .iter()
+        .map(|position| {
+            let bonus = config.loyalty_bonus(position.loyalty_streak)
+                + position.nft_boost(config)
+                + config.dual_boost(position.boost_staked, position.staked_amount());
+            let curve = position.stake_entries.iter().map(|e| entry_curve(e, epoch, bonus) as u128).sum();
+            (position.owner, curve)
+        })
+        .collect();
+    let total_curve: u128 = curves.iter().map(|(_, curve)| curve).sum();
+    if total_curve == 0 {
+        return vec![];
+    }
+
+    curves
+        .into_iter()
+        .map(|(owner, curve)| (owner, (curve * epoch.reward as u128 / total_curve) as u64))
+        .filter(|(_, amount)| *amount > 0)
+        .collect()
+}
+
+/// Sum of the curves the next epoch would get from settling every position at the end of
+/// `epoch`, to publish with its merkle root. Matches UserStake::catch_up.
+pub fn rolled_total_curve(config: &Config, epoch: &Epoch, positions: &[UserStake]) -> u64 {
+    let next_index = epoch.index + 1;
+    positions
+        .iter()
+        .map(|position| {
+            let bonus = position.applied_bonus(config);
+            position
+                .stake_entries
+                .iter()
+                .filter(|entry| !entry.is_unbonding())
+                .map(|entry| {
+                    let mut entry = entry.clone();
+                    entry.catch_up(next_index, config.epoch_duration, bonus);
+                    entry.boosted_curve
+                })
+                .sum::<u64>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::blank;
+
+    fn payouts(count: u8) -> Vec<(Pubkey, u64)> {
+        (1..=count).map(|i| (Pubkey::new_from_array([i; 32]), i as u64 * 1_000)).collect()
+    }
+
+    #[test]
+    fn proofs_verify_for_every_leaf() {
+        for count in 1..=7 {
+            let payouts = payouts(count);
+            let tree = MerkleTree::from_payouts(3, &payouts);
+            for (i, (owner, amount)) in payouts.iter().enumerate() {
+                assert!(verify(&tree.proof(i), &tree.root(), leaf(3, owner, *amount)));
+            }
+        }
+    }
+
+    #[test]
+    fn pairs_hash_in_sorted_order() {
+        let (a, b) = ([1; 32], [2; 32]);
+        assert_eq!(hash_pair(&a, &b), hash_pair(&b, &a));
+        assert_eq!(hash_pair(&a, &b), hashv(&[NODE_PREFIX, &a, &b]).to_bytes());
+    }
+
+    #[test]
+    fn leaves_and_nodes_are_domain_separated() {
+        let owner = Pubkey::new_from_array([9; 32]);
+        let unprefixed = hashv(&[&5u64.to_le_bytes(), owner.as_ref(), &7u64.to_le_bytes()]).to_bytes();
+        assert_ne!(leaf(5, &owner, 7), unprefixed);
+        assert_ne!(hash_pair(&[1; 32], &[2; 32]), hashv(&[&[1; 32], &[2; 32]]).to_bytes());
+    }
+
+    #[test]
+    fn tampered_claims_fail() {
+        let payouts = payouts(5);
+        let tree = MerkleTree::from_payouts(2, &payouts);
+        let (owner, amount) = payouts[2];
+        let proof = tree.proof(2);
+
+        assert!(!verify(&proof, &tree.root(), leaf(2, &owner, amount + 1)));
+        assert!(!verify(&proof, &tree.root(), leaf(3, &owner, amount)));
+        assert!(!verify(&tree.proof(1), &tree.root(), leaf(2, &owner, amount)));
+        assert!(!verify(&proof[1..], &tree.root(), leaf(2, &owner, amount)));
+    }
+
+    #[test]
+    fn rolled_total_curve_matches_catch_up() {
+        let mut config: Config = blank(Config::LEN);
+        config.epoch_duration = 100;
+        let mut epoch: Epoch = blank(Epoch::LEN);
+        epoch.index = 4;
+
+        let mut position: UserStake = blank(UserStake::LEN);
+        position.dual_boost = 20;
+        let mut locked: StakeEntry = blank(StakeEntry::LEN);
+        locked.id = 0;
+        locked.amount = 10;
+        locked.last_staked_epoch_index = 4; // staked during the merkle epoch
+        locked.lock_period = 2;
+        locked.multiplier = 120;
+        let mut unlocked = locked.clone();
+        unlocked.id = 1;
+        unlocked.last_staked_epoch_index = 1;
+        unlocked.calculated_index = 3;
+        let mut unbonding = locked.clone();
+        unbonding.id = 2;
+        unbonding.unbond_release_time = 1;
+        position.stake_entries = vec![locked, unlocked, unbonding];
+
+        // 10 * 100 at 120% + 20%, plus 10 * 100 unboosted
+        assert_eq!(rolled_total_curve(&config, &epoch, &[position.clone()]), 1_400 + 1_000);
+
+        // Settlement of epoch 5 catches the position up to the same curves
+        position.catch_up(5, 100, &config);
+        let caught_up: u64 = position.stake_entries[..2].iter().map(|e| e.boosted_curve).sum();
+        assert_eq!(caught_up, 2_400);
+    }
+
+    #[test]
+    fn empty_tree_has_zero_root() {
+        assert_eq!(MerkleTree::new(vec![]).root(), [0; 32]);
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+/// Fields are only ever appended, but the account is not migrated in place: a layout change
+/// needs a fresh deployment, see migrations/deploy.ts.
 #[account]
 pub struct Config {
     pub admin: Pubkey,
@@ -14,10 +16,127 @@ pub struct Config {
     pub total_curve: u64,
     pub index: u64,
     pub multiplier: Vec<u64>, // New field: multiplier per lock period (e.g., 1,2,4,8,16)
+    pub liquid_mint: Pubkey,  // liquid share mint, Pubkey::default() until initialized
+    pub unbonding_period: i64, // seconds between request_unstake and complete_unstake, 0 = instant withdraw
+    pub ve_index: u64,        // epoch the vote-escrow totals are checkpointed to
+    pub ve_locked_amount: u64, // stake still locked at ve_index
+    pub ve_end_sum: u128,     // sum of amount * lock end over locked stake
+    pub ve_expiring: [u64; Config::VE_SLOTS], // amount whose lock ends at epoch e, in slot e % VE_SLOTS
+    pub realm: Pubkey,        // spl-governance realm using this pool as voter-weight add-in
+    pub governing_token_mint: Pubkey,
+    pub emission_policy: EmissionPolicy,
+    pub reward_funders: Vec<Pubkey>, // who may top up epoch rewards besides the admin, empty = anyone
+    pub claim_expiry_epochs: u64, // epochs after settlement a reward stays claimable, 0 = never expires
+    pub expired_reward_treasury: Pubkey, // token account receiving swept rewards, Pubkey::default() = back to the pool
+    pub swept_rewards: u64,   // swept rewards waiting to roll into the next epoch
+    pub extra_reward_mints: Vec<Pubkey>, // co-incentive mints, each paid from its [REWARD, mint] vault
+    pub fee_bps: u16,         // protocol cut of every funded epoch reward, at most MAX_FEE_BPS
+    pub treasurer: Pubkey,    // may withdraw accrued fees
+    pub treasury: Pubkey,     // reward-mint token account receiving the fees
+    pub accrued_fees: u64,    // fees held in reward_vault until withdrawn
+    pub referral_bps: u16,    // referrer bonus as a share of the referee's settled reward, reserved per epoch
+    pub loyalty_tiers: Vec<LoyaltyTier>, // ascending by min_streak
+    pub nft_boost: u64,       // multiplier points added to locked entries of positions with a registered NFT, 0 = off
+    pub nft_boost_allowlist: Vec<Pubkey>, // NFT mints, verified collections or verified creators that qualify
+    pub boost_token_mint: Pubkey, // secondary token staked to boost the main stake, Pubkey::default() until initialized
+    pub boost_vault: Pubkey,
+    pub dual_boost_max: u64,  // multiplier points reached at dual_boost_ratio_bps, 0 = off
+    pub dual_boost_ratio_bps: u64, // boost stake per main stake, in bps, that earns the full bonus
+    pub nft_collection: Pubkey, // verified collection staked instead of stake_token_mint, Pubkey::default() = fungible pool
+    pub nft_weight: u64,      // stake amount each NFT counts as
+    pub nft_penalty: u64,     // reward tokens charged for withdrawing an NFT before its lock ends, never raised under stakers
+    pub stake_for_minimum: u64, // smallest stake_for deposit, keeps third parties from filling a position with dust
+    pub vote_lock: i64,       // seconds a position cannot be transferred after its voter weight is recorded
+    pub native_penalties: u64, // early-exit penalties of a native stake pool, held in stake_vault until withdrawn
+}
+
+/// Multiplier points (100 = +1x) added to locked entries once a position's streak reaches `min_streak` epochs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LoyaltyTier {
+    pub min_streak: u64,
+    pub bonus: u64,
+}
+
+impl LoyaltyTier {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum EmissionPolicy {
+    /// Reward is whatever the admin passes to toggle or funds in the schedule.
+    Manual,
+    Constant { reward: u64 },
+    /// Each epoch pays `decay_bps` less than the one before.
+    Decay { initial: u64, decay_bps: u16 },
+    /// Reward halves every `interval` epochs.
+    Halving { initial: u64, interval: u64 },
+    /// Explicit reward per epoch, starting at epoch 1; nothing is emitted past the end.
+    Table { rewards: Vec<u64> },
+}
+
+impl EmissionPolicy {
+    pub const MAX_TABLE: usize = 16;
+    const SCALE: u128 = 1_000_000_000_000_000_000; // fixed-point one for pow_scaled
+    pub const LEN: usize = 1 + 4 + 8 * Self::MAX_TABLE; // tag + largest variant (Table)
+
+    /// Reward for epoch `index`, or None when the policy leaves it to the admin.
+    pub fn reward_for(&self, index: u64) -> Option<u64> {
+        if index == 0 {
+            return Some(0); // pre-staking epoch never pays
+        }
+        let epochs_elapsed = index - 1;
+
+        match self {
+            EmissionPolicy::Manual => None,
+            EmissionPolicy::Constant { reward } => Some(*reward),
+            EmissionPolicy::Decay { initial, decay_bps } => {
+                let retained = Self::pow_scaled((10_000 - *decay_bps as u128) * (Self::SCALE / 10_000), epochs_elapsed);
+                Some((*initial as u128 * retained / Self::SCALE) as u64)
+            }
+            EmissionPolicy::Halving { initial, interval } => {
+                let halvings = epochs_elapsed / interval;
+                Some(if halvings >= 64 { 0 } else { initial >> halvings })
+            }
+            EmissionPolicy::Table { rewards } => {
+                Some(rewards.get(epochs_elapsed as usize).copied().unwrap_or(0))
+            }
+        }
+    }
+
+    /// `base^exp` for a `SCALE` fixed-point `base` at most one, by squaring.
+    fn pow_scaled(mut base: u128, mut exp: u64) -> u128 {
+        let mut result = Self::SCALE;
+        while exp > 0 && result > 0 {
+            if exp & 1 == 1 {
+                result = result * base / Self::SCALE;
+            }
+            base = base * base / Self::SCALE;
+            exp >>= 1;
+        }
+        result
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            EmissionPolicy::Manual | EmissionPolicy::Constant { .. } => true,
+            EmissionPolicy::Decay { decay_bps, .. } => *decay_bps < 10_000,
+            EmissionPolicy::Halving { interval, .. } => *interval > 0,
+            EmissionPolicy::Table { rewards } => rewards.len() <= Self::MAX_TABLE,
+        }
+    }
 }
 
 impl Config {
     pub const MAX_MULTIPLIERS: usize = 5;
+    pub const MAX_REWARD_FUNDERS: usize = 8;
+    pub const MAX_EXTRA_REWARDS: usize = 4;
+    pub const MAX_FEE_BPS: u16 = 1_000; // 10%
+    pub const MAX_REFERRAL_BPS: u16 = 2_000; // 20%
+    pub const MAX_LOYALTY_TIERS: usize = 4;
+    pub const MAX_BONUS: u64 = 500; // +5x, cap on each multiplier bonus source
+    pub const MAX_NFT_BOOST_ALLOWLIST: usize = 8;
+    pub const MAX_LOCK_PERIOD: u64 = 16;
+    pub const VE_SLOTS: usize = Self::MAX_LOCK_PERIOD as usize + 1;
 
     pub const LEN: usize = 
         8 +                      // discriminator
@@ -30,6 +149,217 @@ impl Config {
         32 +                    // reward_token_mint
         32 +                    // reward_vault
         8 +                     // total_staked
+        8 +                     // total_curve
         8 +                     // index
-        4 + 8 * Self::MAX_MULTIPLIERS; // multiplier vec: 4-byte prefix + 8 bytes per entry
+        4 + 8 * Self::MAX_MULTIPLIERS + // multiplier vec: 4-byte prefix + 8 bytes per entry
+        32 +                    // liquid_mint
+        8 +                     // unbonding_period
+        8 +                     // ve_index
+        8 +                     // ve_locked_amount
+        16 +                    // ve_end_sum
+        8 * Self::VE_SLOTS +    // ve_expiring
+        32 +                    // realm
+        32 +                    // governing_token_mint
+        EmissionPolicy::LEN +   // emission_policy
+        4 + 32 * Self::MAX_REWARD_FUNDERS + // reward_funders vec
+        8 +                     // claim_expiry_epochs
+        32 +                    // expired_reward_treasury
+        8 +                     // swept_rewards
+        4 + 32 * Self::MAX_EXTRA_REWARDS + // extra_reward_mints vec
+        2 +                     // fee_bps
+        32 +                    // treasurer
+        32 +                    // treasury
+        8 +                     // accrued_fees
+        2 +                     // referral_bps
+        4 + LoyaltyTier::LEN * Self::MAX_LOYALTY_TIERS + // loyalty_tiers vec
+        8 +                     // nft_boost
+        4 + 32 * Self::MAX_NFT_BOOST_ALLOWLIST + // nft_boost_allowlist vec
+        32 +                    // boost_token_mint
+        32 +                    // boost_vault
+        8 +                     // dual_boost_max
+        8 +                     // dual_boost_ratio_bps
+        32 +                    // nft_collection
+        8 +                     // nft_weight
+        8 +                     // nft_penalty
+        8 +                     // stake_for_minimum
+        8 +                     // vote_lock
+        8;                      // native_penalties
+
+    /// NFT pools stake collection NFTs through stake_nft; the fungible stake paths are closed.
+    pub fn is_nft_pool(&self) -> bool {
+        self.nft_collection != Pubkey::default()
+    }
+
+    /// `claim` mask bit for the primary reward.
+    pub const PRIMARY_REWARD: u8 = 1;
+
+    /// `claim` mask bit for `extra_reward_mints[slot]`.
+    pub fn extra_reward_bit(slot: usize) -> u8 {
+        1 << (slot + 1)
+    }
+
+    /// Books the protocol fee on `reward` and returns what is left for stakers.
+    pub fn take_protocol_fee(&mut self, reward: u64) -> (u64, u64) {
+        let fee = (reward as u128 * self.fee_bps as u128 / 10_000) as u64;
+        self.accrued_fees += fee;
+        (reward - fee, fee)
+    }
+
+    /// Bonus of the highest tier the streak has reached.
+    pub fn loyalty_bonus(&self, streak: u64) -> u64 {
+        self.loyalty_tiers
+            .iter()
+            .rev()
+            .find(|tier| streak >= tier.min_streak)
+            .map_or(0, |tier| tier.bonus)
+    }
+
+    /// Gauge-style bonus from staking the boost token: linear in the boost/main ratio and
+    /// capped at `dual_boost_max` once the ratio reaches `dual_boost_ratio_bps`.
+    pub fn dual_boost(&self, boost_staked: u64, main_staked: u64) -> u64 {
+        if self.dual_boost_max == 0 || main_staked == 0 || boost_staked == 0 {
+            return 0;
+        }
+        // Rounded up, so a tiny main stake cannot reach the full bonus with nothing behind it
+        let full_boost_stake = (main_staked as u128 * self.dual_boost_ratio_bps as u128).div_ceil(10_000);
+        if boost_staked as u128 >= full_boost_stake {
+            return self.dual_boost_max;
+        }
+        (self.dual_boost_max as u128 * boost_staked as u128 / full_boost_stake) as u64
+    }
+
+    pub fn can_fund_rewards(&self, funder: Pubkey) -> bool {
+        funder == self.admin || self.reward_funders.is_empty() || self.reward_funders.contains(&funder)
+    }
+
+    /// Drops locks that ended between the last checkpoint and the current epoch.
+    pub fn checkpoint_voting(&mut self) {
+        while self.ve_index < self.index {
+            self.ve_index += 1;
+            let slot = self.ve_index as usize % Self::VE_SLOTS;
+            let expired = self.ve_expiring[slot];
+            self.ve_locked_amount -= expired;
+            self.ve_end_sum -= expired as u128 * self.ve_index as u128;
+            self.ve_expiring[slot] = 0;
+        }
+    }
+
+    pub fn add_voting_lock(&mut self, amount: u64, lock_end: u64) {
+        self.checkpoint_voting();
+        if lock_end <= self.index {
+            return;
+        }
+        self.ve_locked_amount += amount;
+        self.ve_end_sum += amount as u128 * lock_end as u128;
+        self.ve_expiring[lock_end as usize % Self::VE_SLOTS] += amount;
+    }
+
+    pub fn remove_voting_lock(&mut self, amount: u64, lock_end: u64) {
+        self.checkpoint_voting();
+        if lock_end <= self.index {
+            return;
+        }
+        self.ve_locked_amount -= amount;
+        self.ve_end_sum -= amount as u128 * lock_end as u128;
+        self.ve_expiring[lock_end as usize % Self::VE_SLOTS] -= amount;
+    }
+
+    /// Pool-wide voting power: sum of amount * remaining lock / MAX_LOCK_PERIOD.
+    pub fn total_voting_power(&self) -> u64 {
+        let mut locked_amount = self.ve_locked_amount;
+        let mut end_sum = self.ve_end_sum;
+        // Apply expirations a checkpoint has not processed yet
+        for index in self.ve_index + 1..=self.index {
+            let expired = self.ve_expiring[index as usize % Self::VE_SLOTS];
+            locked_amount -= expired;
+            end_sum -= expired as u128 * index as u128;
+        }
+
+        let remaining = end_sum - locked_amount as u128 * self.index as u128;
+        (remaining / Self::MAX_LOCK_PERIOD as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::blank;
+
+    fn config() -> Config {
+        blank(Config::LEN)
+    }
+
+    #[test]
+    fn voting_power_decays_and_checkpoints_drop_ended_locks() {
+        let mut config = config();
+        config.index = 1;
+        config.add_voting_lock(160, 5);
+        config.add_voting_lock(32, 17);
+        // 160 * 4 / 16 + 32 * 16 / 16
+        assert_eq!(config.total_voting_power(), 72);
+
+        // Ended locks stop counting before any checkpoint runs
+        config.index = 5;
+        assert_eq!(config.total_voting_power(), 24);
+        config.checkpoint_voting();
+        assert_eq!((config.ve_index, config.ve_locked_amount), (5, 32));
+        assert_eq!(config.total_voting_power(), 24);
+
+        // A lock ending in the slot epoch 3 used is not confused with it
+        config.add_voting_lock(16, 20);
+        assert_eq!(config.total_voting_power(), 24 + 15);
+
+        // Exits after the lock ended have nothing left to remove
+        config.remove_voting_lock(160, 5);
+        config.remove_voting_lock(32, 17);
+        assert_eq!(config.total_voting_power(), 15);
+
+        config.index = 20;
+        assert_eq!(config.total_voting_power(), 0);
+        config.checkpoint_voting();
+        assert_eq!((config.ve_locked_amount, config.ve_end_sum), (0, 0));
+    }
+
+    #[test]
+    fn dual_boost_needs_boost_stake() {
+        let mut config = config();
+        config.dual_boost_max = 50;
+        config.dual_boost_ratio_bps = 1_000;
+
+        // 1 * 10% floors to 0, which used to hand out the full bonus for nothing
+        assert_eq!(config.dual_boost(0, 1), 0);
+        assert_eq!(config.dual_boost(0, 1_000), 0);
+        assert_eq!(config.dual_boost(1, 1), 50);
+    }
+
+    #[test]
+    fn dual_boost_is_linear_up_to_the_ratio() {
+        let mut config = config();
+        config.dual_boost_max = 50;
+        config.dual_boost_ratio_bps = 1_000;
+
+        assert_eq!(config.dual_boost(50, 1_000), 25);
+        assert_eq!(config.dual_boost(100, 1_000), 50);
+        assert_eq!(config.dual_boost(500, 1_000), 50);
+        // Full stake of 100.1 rounds up to 101
+        assert_eq!(config.dual_boost(100, 1_001), 49);
+    }
+
+    #[test]
+    fn decay_matches_compounding() {
+        let policy = EmissionPolicy::Decay { initial: 1_000_000, decay_bps: 1_000 };
+        assert_eq!(policy.reward_for(1), Some(1_000_000));
+        assert_eq!(policy.reward_for(2), Some(900_000));
+        assert_eq!(policy.reward_for(4), Some(729_000));
+        assert_eq!(policy.reward_for(11), Some(348_678));
+    }
+
+    #[test]
+    fn decay_reaches_zero_without_iterating() {
+        let policy = EmissionPolicy::Decay { initial: u64::MAX, decay_bps: 5_000 };
+        assert_eq!(policy.reward_for(u64::MAX), Some(0));
+
+        let flat = EmissionPolicy::Decay { initial: 500, decay_bps: 0 };
+        assert_eq!(flat.reward_for(1_000_000), Some(500));
+    }
 }
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Marks a merkle payout as claimed; its PDA can only be created once per epoch and owner.
+#[account]
+pub struct MerkleClaim {
+    pub epoch_index: u64,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+impl MerkleClaim {
+    pub const LEN: usize = 8 + 32 + 8;
+}
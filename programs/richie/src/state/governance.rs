@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+// Layouts of the spl-governance voter-weight add-in records. Anchor's `account:<Name>`
+// discriminators are the ones spl-governance checks, so the names must stay as they are.
+
+#[account]
+pub struct VoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: Option<u64>, // slot
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + (1 + 8) + (1 + 1) + (1 + 32) + 8;
+}
+
+#[account]
+pub struct MaxVoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+    pub max_voter_weight_expiry: Option<u64>, // slot
+    pub reserved: [u8; 8],
+}
+
+impl MaxVoterWeightRecord {
+    pub const LEN: usize = 32 + 32 + 8 + (1 + 8) + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
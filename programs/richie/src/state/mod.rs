@@ -1,5 +1,21 @@
 pub mod config;
 pub mod user_stake;
+pub mod governance;
+pub mod schedule;
+pub mod distributor;
+pub mod referral;
+pub mod nft_boost;
 
 pub use config::*;
-pub use user_stake::*;
\ No newline at end of file
+pub use user_stake::*;
+pub use governance::*;
+pub use schedule::*;
+pub use distributor::*;
+pub use referral::*;
+pub use nft_boost::*;
+
+/// Account with every field zeroed, for unit tests: empty vectors, default keys and zero amounts.
+#[cfg(test)]
+pub(crate) fn blank<T: anchor_lang::AnchorDeserialize>(len: usize) -> T {
+    T::deserialize(&mut &vec![0u8; len][..]).unwrap()
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Position a boost NFT is registered on, so one NFT boosts one position at a time.
+#[account]
+pub struct NftBoostLock {
+    pub user_stake: Pubkey,
+}
+
+impl NftBoostLock {
+    pub const LEN: usize = 32;
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Who referred `referee`, fixed at the referee's first referred stake.
+#[account]
+pub struct Referral {
+    pub referee: Pubkey,
+    pub referrer: Pubkey,
+    pub total_earned: u64, // referral rewards credited to the referrer so far
+}
+
+impl Referral {
+    pub const LEN: usize = 32 + 32 + 8;
+    pub const MAX_DEPTH: usize = 8; // referrer chains above a new referee, walked to rule out cycles
+}
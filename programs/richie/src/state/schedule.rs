@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct EmissionSchedule {
+    pub queue: Vec<ScheduledEmission>, // funded epochs, consumed front first by advance_epoch
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScheduledEmission {
+    pub reward: u64,
+    pub crank_fee: u64, // paid to whoever advances into this epoch
+}
+
+impl ScheduledEmission {
+    pub const LEN: usize = 8 + 8;
+}
+
+impl EmissionSchedule {
+    pub const MAX_EPOCHS: usize = 52;
+
+    pub const LEN: usize = 4 + ScheduledEmission::LEN * Self::MAX_EPOCHS;
+
+    /// Reconciles the escrow `funded` for the epoch being opened with the `wanted` policy amount.
+    /// Unused escrow is requeued for the next epoch; a shortfall is drawn from later funded epochs
+    /// and clamped to what is escrowed, so the crank never stalls on an under-funded policy.
+    pub fn draw(&mut self, funded: u64, wanted: u64) -> u64 {
+        if wanted <= funded {
+            let unused = funded - wanted;
+            if unused > 0 {
+                match self.queue.first_mut() {
+                    Some(next) => next.reward += unused,
+                    None => self.queue.push(ScheduledEmission { reward: unused, crank_fee: 0 }),
+                }
+            }
+            return wanted;
+        }
+
+        let mut shortfall = wanted - funded;
+        for next in self.queue.iter_mut() {
+            let taken = shortfall.min(next.reward);
+            next.reward -= taken;
+            shortfall -= taken;
+            if shortfall == 0 {
+                break;
+            }
+        }
+        wanted - shortfall
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(rewards: &[u64]) -> EmissionSchedule {
+        EmissionSchedule {
+            queue: rewards.iter().map(|reward| ScheduledEmission { reward: *reward, crank_fee: 0 }).collect(),
+        }
+    }
+
+    fn rewards(schedule: &EmissionSchedule) -> Vec<u64> {
+        schedule.queue.iter().map(|e| e.reward).collect()
+    }
+
+    #[test]
+    fn unused_escrow_is_requeued() {
+        let mut queue = schedule(&[100]);
+        assert_eq!(queue.draw(150, 120), 120);
+        assert_eq!(rewards(&queue), vec![130]);
+
+        let mut empty = schedule(&[]);
+        assert_eq!(empty.draw(150, 0), 0);
+        assert_eq!(rewards(&empty), vec![150]);
+    }
+
+    #[test]
+    fn shortfall_is_drawn_from_later_epochs_and_clamped() {
+        let mut queue = schedule(&[30, 50]);
+        assert_eq!(queue.draw(100, 160), 160);
+        assert_eq!(rewards(&queue), vec![0, 20]);
+
+        // Policy asks for more than everything escrowed: pay what there is instead of reverting
+        assert_eq!(queue.draw(0, 500), 20);
+        assert_eq!(rewards(&queue), vec![0, 0]);
+    }
+}
@@ -1,7 +1,7 @@
-use std::sync::atomic::AtomicBool;
-
 use anchor_lang::prelude::*;
 
+use crate::{ state::Config, error::RichieError };
+
 #[account]
 pub struct Stakes {
     pub list: Vec<Pubkey>,
@@ -17,10 +17,35 @@ pub struct UserStake {
     pub owner: Pubkey,
     pub stake_entries: Vec<StakeEntry>,
     pub pending_reward: u64,
+    pub next_entry_id: u64, // id assigned to the next stake entry, never reused
+    pub operator: Pubkey,   // delegate allowed to act on the position, Pubkey::default() if none
+    pub operator_permissions: u8, // bitmask of OPERATOR_* permissions
+    pub reward_buckets: Vec<RewardBucket>, // pending_reward split by the epoch it was settled in
+    pub extra_pending_rewards: [u64; Config::MAX_EXTRA_REWARDS], // per config.extra_reward_mints slot, never expire
+    pub referrer: Pubkey, // owner of the position that referred this one, Pubkey::default() if none
+    pub loyalty_streak: u64, // consecutive epochs settled with stake and no early exit
+    pub streak_index: u64,   // last epoch counted in loyalty_streak
+    pub boost_nft_mint: Pubkey,    // registered boost NFT, Pubkey::default() if none
+    pub boost_nft_account: Pubkey, // owner's token account holding boost_nft_mint
+    pub boost_staked: u64, // boost token held in the boost vault for this position
+    pub dual_boost: u64,   // dual-token bonus currently applied to the locked entries
+    pub vote_locked_until: i64, // transfer_position is refused until then, so voted stake cannot vote again elsewhere
+    pub nft_boost_applied: u64, // NFT bonus carried by every locked entry's curve in the running epoch
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RewardBucket {
+    pub epoch_index: u64,
+    pub amount: u64,
+}
+
+impl RewardBucket {
+    pub const LEN: usize = 8 + 8;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct StakeEntry {
+    pub id: u64,
     pub amount: u64,
     pub last_staked_epoch_index: u64,
     pub lock_period: u8,
@@ -28,15 +53,290 @@ pub struct StakeEntry {
     pub base_curve: u64,
     pub boosted_curve: u64,
     pub calculated_index: u64,
+    pub receipt_mint: Pubkey, // Pubkey::default() when no receipt NFT was minted
+    pub pending_reward: u64,  // rewards owed to the receipt holder, never expire
+    pub extra_pending_rewards: [u64; Config::MAX_EXTRA_REWARDS], // receipt holder's extra rewards, per config.extra_reward_mints slot
+    pub unbond_release_time: i64, // when complete_unstake may pay out, 0 while the entry is staked
+    pub unbond_penalty: u64,  // early-exit penalty fixed at request_unstake
+    pub nft_mint: Pubkey,     // NFT held in its [NFT_ESCROW, mint] escrow, Pubkey::default() for fungible stake
+}
+
+impl StakeEntry {
+    pub const LEN: usize = 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 32 + 8 + 8 * Config::MAX_EXTRA_REWARDS + 8 + 8 + 32;
+
+    /// Locked curve for `base_curve`: multiplier and bonus are percentages, 100 = 1x.
+    pub fn boost(base_curve: u64, multiplier: u64, bonus: u64) -> u64 {
+        (base_curve as u128 * (multiplier + bonus) as u128 / 100) as u64
+    }
+
+    /// Rolls the curves of an entry that missed settlement, as happens in merkle epochs, to what
+    /// settlement would have left for epoch `index`: a full `duration`, boosted if it was locked.
+    pub fn catch_up(&mut self, index: u64, duration: i64, bonus: u64) {
+        if self.is_unbonding() || self.last_staked_epoch_index >= index || self.calculated_index + 1 >= index {
+            return;
+        }
+        self.roll_forward(index - 1, duration, bonus);
+        self.calculated_index = index - 1;
+    }
+
+    /// Sets the curves settlement of epoch `index` leaves for the next one: a full `duration`,
+    /// with the lock multiplier and `bonus` applied while the entry is still locked.
+    pub fn roll_forward(&mut self, index: u64, duration: i64, bonus: u64) {
+        self.base_curve = self.amount * duration as u64;
+        self.boosted_curve = if self.is_locked_at(index) {
+            Self::boost(self.base_curve, self.multiplier, bonus)
+        } else {
+            self.base_curve
+        };
+    }
+
+    /// First epoch index at which the entry can be withdrawn without penalty.
+    pub fn lock_end(&self) -> u64 {
+        self.last_staked_epoch_index + self.lock_period as u64
+    }
+
+    /// Vote-escrow weight: amount scaled by the epochs left on the lock, decaying to zero at lock end.
+    pub fn voting_power(&self, current_index: u64) -> u64 {
+        if self.is_unbonding() {
+            return 0;
+        }
+        let remaining = self.lock_end().saturating_sub(current_index);
+        (self.amount as u128 * remaining as u128 / Config::MAX_LOCK_PERIOD as u128) as u64
+    }
+
+    /// Whether the lock multiplier applies in epoch `index`; pre-staking entries lock from epoch 1.
+    pub fn is_locked_at(&self, index: u64) -> bool {
+        self.last_staked_epoch_index.max(1) + self.lock_period as u64 > index
+    }
+
+    /// Entries with a receipt are controlled by whoever holds the receipt NFT.
+    pub fn has_receipt(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    /// True while the receipt holder still has primary or extra rewards to claim on the entry.
+    pub fn has_pending_rewards(&self) -> bool {
+        self.pending_reward > 0 || self.extra_pending_rewards.iter().any(|amount| *amount > 0)
+    }
+
+    /// NFT entries stake `amount` as weight and only leave through withdraw_nft.
+    pub fn is_nft(&self) -> bool {
+        self.nft_mint != Pubkey::default()
+    }
+
+    /// Unbonding entries no longer earn and wait for complete_unstake or cancel_unstake.
+    pub fn is_unbonding(&self) -> bool {
+        self.unbond_release_time != 0
+    }
 }
 
 impl UserStake {
     pub const MAX_ENTRIES: usize = 20;
+    pub const MAX_REWARD_BUCKETS: usize = 8;
 
     pub const LEN: usize =
         32 +                            // owner
-        4 + (8 + 8 + 1 + 8 + 8 + 8) * Self::MAX_ENTRIES + // Vec<StakeEntry>: 4 bytes + N * entry size
-        8;                              // pending_reward
+        4 + StakeEntry::LEN * Self::MAX_ENTRIES + // Vec<StakeEntry>: 4 bytes + N * entry size
+        8 +                             // pending_reward
+        8 +                             // next_entry_id
+        32 +                            // operator
+        1 +                             // operator_permissions
+        4 + RewardBucket::LEN * Self::MAX_REWARD_BUCKETS + // reward_buckets
+        8 * Config::MAX_EXTRA_REWARDS + // extra_pending_rewards
+        32 +                            // referrer
+        8 +                             // loyalty_streak
+        8 +                             // streak_index
+        32 +                            // boost_nft_mint
+        32 +                            // boost_nft_account
+        8 +                             // boost_staked
+        8 +                             // dual_boost
+        8 +                             // vote_locked_until
+        8;                              // nft_boost_applied
+
+    pub const OPERATOR_CLAIM: u8 = 1 << 0;
+    pub const OPERATOR_COMPOUND: u8 = 1 << 1;
+    pub const OPERATOR_EXTEND_LOCK: u8 = 1 << 2;
+    pub const OPERATOR_ALL: u8 = Self::OPERATOR_CLAIM | Self::OPERATOR_COMPOUND | Self::OPERATOR_EXTEND_LOCK;
+
+    /// True for the owner, or for the operator when it holds `permission`.
+    pub fn is_authorized(&self, key: Pubkey, permission: u8) -> bool {
+        key == self.owner
+            || (self.operator != Pubkey::default()
+                && key == self.operator
+                && self.operator_permissions & permission == permission)
+    }
+
+    /// Operators may only pay out to accounts of the owner; the owner may pay anywhere.
+    pub fn check_recipient(&self, authority: Pubkey, recipient: Pubkey) -> Result<()> {
+        if authority != self.owner {
+            require_keys_eq!(recipient, self.owner, RichieError::InvalidRecipient);
+        }
+        Ok(())
+    }
+
+    /// Hands out the next entry id and advances the counter.
+    pub fn allocate_entry_id(&mut self) -> u64 {
+        let id = self.next_entry_id;
+        self.next_entry_id += 1;
+        id
+    }
+
+    pub fn entry(&self, id: u64) -> Result<&StakeEntry> {
+        self.stake_entries
+            .iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| RichieError::EntryNotFound.into())
+    }
+
+    pub fn entry_mut(&mut self, id: u64) -> Result<&mut StakeEntry> {
+        self.stake_entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| RichieError::EntryNotFound.into())
+    }
+
+    /// Removes the entry with the given id and returns it.
+    pub fn remove_entry(&mut self, id: u64) -> Result<StakeEntry> {
+        let position = self
+            .stake_entries
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or(RichieError::EntryNotFound)?;
+        Ok(self.stake_entries.remove(position))
+    }
+
+    /// Principal still in the pool, the main side of the dual-token boost.
+    pub fn staked_amount(&self) -> u64 {
+        self.stake_entries.iter().filter(|e| !e.is_unbonding()).map(|e| e.amount).sum()
+    }
+
+    /// Multiplier points the registered NFT adds to locked entries from the next settlement on.
+    pub fn nft_boost(&self, config: &Config) -> u64 {
+        if self.boost_nft_mint == Pubkey::default() {
+            0
+        } else {
+            config.nft_boost
+        }
+    }
+
+    /// Bonus carried by the locked curves of the running epoch.
+    pub fn applied_bonus(&self, config: &Config) -> u64 {
+        config.loyalty_bonus(self.loyalty_streak) + self.nft_boost_applied + self.dual_boost
+    }
+
+    /// Brings entries that missed settlement up to epoch `index`, see StakeEntry::catch_up.
+    pub fn catch_up(&mut self, index: u64, duration: i64, config: &Config) {
+        let bonus = self.applied_bonus(config);
+        for entry in self.stake_entries.iter_mut() {
+            entry.catch_up(index, duration, bonus);
+        }
+    }
+
+    /// Counts epoch `index` towards the loyalty streak, restarting it if an epoch was missed.
+    pub fn extend_streak(&mut self, index: u64) {
+        if self.streak_index == index {
+            return;
+        }
+        self.loyalty_streak = if self.streak_index + 1 == index { self.loyalty_streak + 1 } else { 1 };
+        self.streak_index = index;
+    }
+
+    /// Adds a settled reward to pending_reward, remembering the epoch it was earned in.
+    /// Buckets stay sorted by epoch; merkle claims can credit an epoch older than the newest bucket.
+    pub fn credit_reward(&mut self, epoch_index: u64, amount: u64) {
+        if amount == 0 {
+            return;
+        }
+        self.pending_reward = self.pending_reward.saturating_add(amount);
+
+        match self.reward_buckets.iter().position(|bucket| bucket.epoch_index >= epoch_index) {
+            Some(i) if self.reward_buckets[i].epoch_index == epoch_index => self.reward_buckets[i].amount += amount,
+            Some(i) => self.reward_buckets.insert(i, RewardBucket { epoch_index, amount }),
+            None => self.reward_buckets.push(RewardBucket { epoch_index, amount }),
+        }
+
+        // Out of room: fold the oldest bucket into the next one, which only pushes its deadline back
+        if self.reward_buckets.len() > Self::MAX_REWARD_BUCKETS {
+            let oldest = self.reward_buckets.remove(0);
+            self.reward_buckets[0].amount += oldest.amount;
+        }
+    }
+
+    /// Pays out everything pending and forgets the buckets.
+    pub fn take_pending_reward(&mut self) -> u64 {
+        self.reward_buckets.clear();
+        std::mem::take(&mut self.pending_reward)
+    }
+
+    /// Drops rewards settled more than `expiry_epochs` epochs before `current_index` and returns their sum.
+    /// Only the position's own pending_reward expires; receipt entries keep theirs until claimed.
+    pub fn expire_rewards(&mut self, current_index: u64, expiry_epochs: u64) -> u64 {
+        let mut expired = 0;
+        self.reward_buckets.retain(|bucket| {
+            if bucket.epoch_index + expiry_epochs < current_index {
+                expired += bucket.amount;
+                false
+            } else {
+                true
+            }
+        });
+        // Buckets can outlive a pending_reward already drawn down elsewhere; never sweep more than is owed
+        let expired = expired.min(self.pending_reward);
+        self.pending_reward -= expired;
+        expired
+    }
+
+    pub fn voting_power(&self, current_index: u64) -> u64 {
+        self.stake_entries.iter().map(|e| e.voting_power(current_index)).sum()
+    }
+
+    /// Folds `entry_ids[1..]` into `entry_ids[0]`. Entries must either all be past their lock
+    /// or share lock tier, start epoch and multiplier, and must be settled up to the same epoch.
+    pub fn merge_entries(&mut self, entry_ids: &[u64], current_index: u64) -> Result<()> {
+        require!(entry_ids.len() >= 2, RichieError::InvalidMerge);
+        for (i, id) in entry_ids.iter().enumerate() {
+            require!(!entry_ids[..i].contains(id), RichieError::InvalidMerge);
+        }
+
+        let target = self.entry(entry_ids[0])?.clone();
+        require!(!target.has_receipt(), RichieError::ReceiptControlled);
+        require!(!target.is_nft(), RichieError::NftEntry);
+        require!(!target.is_unbonding(), RichieError::EntryUnbonding);
+        let target_expired = target.lock_end() <= current_index;
+
+        let mut amount = target.amount;
+        let mut base_curve = target.base_curve;
+        let mut boosted_curve = target.boosted_curve;
+
+        for id in entry_ids[1..].iter() {
+            let entry = self.entry(*id)?;
+            require!(!entry.has_receipt(), RichieError::ReceiptControlled);
+            require!(!entry.is_nft(), RichieError::NftEntry);
+            require!(!entry.is_unbonding(), RichieError::EntryUnbonding);
+
+            // Entries must be at the same settlement point so their curves describe the same epoch
+            require!(entry.calculated_index == target.calculated_index, RichieError::InvalidMerge);
+
+            let expired = entry.lock_end() <= current_index;
+            let same_tier = entry.lock_period == target.lock_period
+                && entry.last_staked_epoch_index == target.last_staked_epoch_index
+                && entry.multiplier == target.multiplier;
+            require!((expired && target_expired) || same_tier, RichieError::InvalidMerge);
+
+            amount += entry.amount;
+            base_curve += entry.base_curve;
+            boosted_curve += entry.boosted_curve;
+        }
+
+        // Summing the curves keeps the entry's share of epoch.total_curve, and so its reward, unchanged
+        self.stake_entries.retain(|e| !entry_ids[1..].contains(&e.id));
+        let merged = self.entry_mut(entry_ids[0])?;
+        merged.amount = amount;
+        merged.base_curve = base_curve;
+        merged.boosted_curve = boosted_curve;
+
+        Ok(())
+    }
 }
 
 #[account]
@@ -48,9 +348,209 @@ pub struct Epoch {
     pub reward: u64,
     pub total_curve: u64,
     pub total_staked_amount: u64,
-    pub claimable: bool
+    pub claimable: bool,
+    pub allocated: u64,    // reward credited to stakers during settlement
+    pub carried_over: u64, // part of `reward` rolled in from the previous epoch's unallocated remainder
+    pub merkle_root: [u8; 32], // payouts computed off-chain, all zeroes when settled on-chain
+    pub merkle_claimed: u64,   // sum of merkle payouts claimed so far
+    pub extra_rewards: [u64; Config::MAX_EXTRA_REWARDS], // per config.extra_reward_mints slot, settled on-chain; merkle epochs roll them over
+    pub extra_allocated: [u64; Config::MAX_EXTRA_REWARDS],
+    pub protocol_fee: u64, // taken from the funded reward before it reached `reward`
+    pub referral_reserve: u64, // set aside from each funding for referrer bonuses, not part of `reward`
+    pub referral_paid: u64,    // referrer bonuses credited out of referral_reserve
 }
 
 impl Epoch {
-    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
-}
\ No newline at end of file
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 32 + 8 + 16 * Config::MAX_EXTRA_REWARDS + 8 + 8 + 8;
+
+    /// Reward left over after settlement, to be rolled into the next epoch.
+    pub fn unallocated(&self) -> u64 {
+        self.reward.saturating_sub(self.allocated)
+    }
+
+    /// Referral reserve left over after settlement, rolled into the next epoch with `unallocated`.
+    pub fn referral_unpaid(&self) -> u64 {
+        self.referral_reserve.saturating_sub(self.referral_paid)
+    }
+
+    /// Adds `amount` to the epoch, keeping back the share referrers can claim on it. Bonuses are
+    /// `referral_bps` of stakers' shares of `reward`, so they always fit in the reserve.
+    pub fn fund(&mut self, amount: u64, referral_bps: u16) {
+        let reserve = (amount as u128 * referral_bps as u128 / 10_000) as u64;
+        self.referral_reserve += reserve;
+        self.reward += amount - reserve;
+    }
+
+    pub fn extra_unallocated(&self, slot: usize) -> u64 {
+        self.extra_rewards[slot].saturating_sub(self.extra_allocated[slot])
+    }
+
+    pub fn is_merkle(&self) -> bool {
+        self.merkle_root != [0; 32]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::blank;
+
+    fn buckets(user_stake: &UserStake) -> Vec<(u64, u64)> {
+        user_stake.reward_buckets.iter().map(|b| (b.epoch_index, b.amount)).collect()
+    }
+
+    fn locked_entry(id: u64, amount: u64, lock_period: u8) -> StakeEntry {
+        let mut entry: StakeEntry = blank(StakeEntry::LEN);
+        entry.id = id;
+        entry.amount = amount;
+        entry.last_staked_epoch_index = 2;
+        entry.lock_period = lock_period;
+        entry.multiplier = 100 + lock_period as u64 * 10;
+        entry.base_curve = amount * 10;
+        entry.boosted_curve = StakeEntry::boost(entry.base_curve, entry.multiplier, 0);
+        entry.calculated_index = 3;
+        entry
+    }
+
+    fn position(entries: Vec<StakeEntry>) -> UserStake {
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        user_stake.stake_entries = entries;
+        user_stake
+    }
+
+    #[test]
+    fn credits_out_of_order_merge_by_epoch() {
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        user_stake.credit_reward(5, 50);
+        user_stake.credit_reward(3, 30); // merkle claim for an older epoch
+        user_stake.credit_reward(5, 5);
+        user_stake.credit_reward(4, 40);
+        user_stake.credit_reward(3, 3);
+
+        assert_eq!(buckets(&user_stake), vec![(3, 33), (4, 40), (5, 55)]);
+        assert_eq!(user_stake.pending_reward, 128);
+
+        // Epoch 3 expires on its own deadline, not epoch 5's
+        assert_eq!(user_stake.expire_rewards(6, 2), 33);
+        assert_eq!(buckets(&user_stake), vec![(4, 40), (5, 55)]);
+    }
+
+    #[test]
+    fn rewards_expire_after_the_claim_window() {
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        user_stake.credit_reward(3, 10);
+        user_stake.credit_reward(4, 20);
+
+        // Epoch 3 stays claimable through epoch 5 with a two-epoch window
+        assert_eq!(user_stake.expire_rewards(5, 2), 0);
+        assert_eq!(user_stake.expire_rewards(6, 2), 10);
+        assert_eq!(buckets(&user_stake), vec![(4, 20)]);
+        assert_eq!(user_stake.pending_reward, 20);
+
+        // Never sweeps more than is still pending
+        user_stake.pending_reward = 5;
+        assert_eq!(user_stake.expire_rewards(7, 2), 5);
+        assert_eq!(user_stake.pending_reward, 0);
+        assert!(user_stake.reward_buckets.is_empty());
+    }
+
+    #[test]
+    fn receipt_rewards_do_not_expire() {
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        let mut entry: StakeEntry = blank(StakeEntry::LEN);
+        entry.receipt_mint = Pubkey::new_unique();
+        entry.pending_reward = 70;
+        user_stake.stake_entries.push(entry);
+        user_stake.credit_reward(1, 20);
+
+        assert_eq!(user_stake.expire_rewards(10, 2), 20);
+        assert_eq!(user_stake.pending_reward, 0);
+        assert_eq!(user_stake.stake_entries[0].pending_reward, 70);
+    }
+
+    #[test]
+    fn full_buckets_fold_the_oldest_forward() {
+        let mut user_stake: UserStake = blank(UserStake::LEN);
+        for epoch in (2..=UserStake::MAX_REWARD_BUCKETS as u64 + 1).rev() {
+            user_stake.credit_reward(epoch, 10);
+        }
+        user_stake.credit_reward(1, 7);
+
+        assert_eq!(user_stake.reward_buckets.len(), UserStake::MAX_REWARD_BUCKETS);
+        assert_eq!(buckets(&user_stake)[0], (2, 17));
+        assert_eq!(user_stake.pending_reward, 10 * UserStake::MAX_REWARD_BUCKETS as u64 + 7);
+    }
+
+    #[test]
+    fn settlement_rolls_the_multiplier_forward_as_a_percentage() {
+        let mut entry: StakeEntry = blank(StakeEntry::LEN);
+        entry.amount = 10;
+        entry.last_staked_epoch_index = 1;
+        entry.lock_period = 4;
+        entry.multiplier = 150;
+
+        entry.roll_forward(2, 100, 0);
+        assert_eq!(entry.base_curve, 1_000);
+        assert_eq!(entry.boosted_curve, StakeEntry::boost(1_000, 150, 0));
+        // Settlement used to multiply by the raw percentage, weighting a 1.5x lock as 150x
+        assert_eq!(entry.boosted_curve, 1_500);
+        assert_ne!(entry.boosted_curve, entry.base_curve * entry.multiplier);
+
+        // Past the lock only the base curve counts
+        entry.roll_forward(5, 100, 20);
+        assert_eq!(entry.boosted_curve, 1_000);
+    }
+
+    #[test]
+    fn merging_sums_amounts_and_curves() {
+        let mut user_stake = position(vec![locked_entry(1, 100, 4), locked_entry(2, 50, 4), locked_entry(3, 30, 4)]);
+        user_stake.merge_entries(&[2, 1, 3], 4).unwrap();
+
+        assert_eq!(user_stake.stake_entries.len(), 1);
+        let merged = &user_stake.stake_entries[0];
+        assert_eq!((merged.id, merged.amount), (2, 180));
+        assert_eq!(merged.base_curve, 1_800);
+        assert_eq!(merged.boosted_curve, 2_520);
+    }
+
+    #[test]
+    fn only_expired_locks_merge_across_tiers() {
+        let mut user_stake = position(vec![locked_entry(1, 100, 2), locked_entry(2, 50, 4)]);
+        assert!(user_stake.merge_entries(&[1, 2], 4).is_err());
+        // Both locks have ended by epoch 6
+        user_stake.merge_entries(&[1, 2], 6).unwrap();
+        assert_eq!(user_stake.stake_entries[0].amount, 150);
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_or_controlled_entries() {
+        let mut unsettled = locked_entry(2, 50, 4);
+        unsettled.calculated_index = 2;
+        let mut receipt = locked_entry(3, 50, 4);
+        receipt.receipt_mint = Pubkey::new_unique();
+        let mut unbonding = locked_entry(4, 50, 4);
+        unbonding.unbond_release_time = 1;
+        let mut user_stake = position(vec![locked_entry(1, 100, 4), unsettled, receipt, unbonding]);
+
+        assert!(user_stake.merge_entries(&[1, 2], 4).is_err());
+        assert!(user_stake.merge_entries(&[1, 3], 4).is_err());
+        assert!(user_stake.merge_entries(&[1, 4], 4).is_err());
+        assert!(user_stake.merge_entries(&[1, 1], 4).is_err());
+        assert!(user_stake.merge_entries(&[1], 4).is_err());
+        assert_eq!(user_stake.stake_entries.len(), 4);
+    }
+
+    #[test]
+    fn boost_scales_by_percentage() {
+        assert_eq!(StakeEntry::boost(1_000, 100, 0), 1_000);
+        assert_eq!(StakeEntry::boost(1_000, 150, 0), 1_500);
+        assert_eq!(StakeEntry::boost(1_000, 150, 25), 1_750);
+    }
+
+    #[test]
+    fn boost_does_not_overflow_before_scaling() {
+        // base_curve * 200 alone would overflow a u64
+        let base_curve = u64::MAX / 100;
+        assert_eq!(StakeEntry::boost(base_curve, 100, Config::MAX_BONUS / 5), 2 * base_curve);
+    }
+}
@@ -101,6 +101,7 @@ describe("richie", () => {
             owner: owner.publicKey,
             config,
             epoch,
+            previousEpoch: null,
             rewardMint: rewardTokenMint,
             rewardMintTokenAccount,
             rewardVault,
@@ -146,6 +147,12 @@ describe("richie", () => {
             stakeVault,
             epoch,
             stakes,
+            referrerStake: null,
+            referral: null,
+            receiptMint: null,
+            userReceiptAccount: null,
+            associatedTokenProgram: null,
+            boostNftAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId
           },
@@ -167,6 +174,15 @@ describe("richie", () => {
         const user = users[i];
         const index = new anchor.BN(0);
         const userStake = user.publicKey
+        const hasReferrer = !user.account.referrer.equals(PublicKey.default);
+        const [referrerStake] = PublicKey.findProgramAddressSync(
+          [Buffer.from("user"), user.account.referrer.toBuffer()],
+          program.programId
+        );
+        const [referral] = PublicKey.findProgramAddressSync(
+          [Buffer.from("referral"), user.account.owner.toBuffer()],
+          program.programId
+        );
      
         const [epoch] = PublicKey.findProgramAddressSync(
           [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
@@ -183,6 +199,9 @@ describe("richie", () => {
               user: user.account.owner,
               userStake,
               stakes,
+              referrerStake: hasReferrer ? referrerStake : null,
+              referral: hasReferrer ? referral : null,
+              boostNftAccount: user.account.boostNftMint.equals(PublicKey.default) ? null : user.account.boostNftAccount,
               tokenProgram: TOKEN_PROGRAM_ID,
               systemProgram: SystemProgram.programId
             },
@@ -204,6 +223,11 @@ describe("richie", () => {
         [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
+      // Whatever epoch 1 did not hand out during settlement rolls into this one
+      const [previousEpoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.subn(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
 
       const rewardMintTokenAccount = getAssociatedTokenAddressSync(
         rewardTokenMint,
@@ -217,6 +241,7 @@ describe("richie", () => {
             owner: owner.publicKey,
             config,
             epoch,
+            previousEpoch,
             rewardMint: rewardTokenMint,
             rewardMintTokenAccount,
             rewardVault,
@@ -232,6 +257,179 @@ describe("richie", () => {
     }
   });
   /*
+  it("Fund the emission schedule", async() => {
+    try {
+      const [schedule] = PublicKey.findProgramAddressSync(
+        [Buffer.from("schedule")],
+        program.programId
+      );
+      const rewardMintTokenAccount = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        owner.publicKey
+      );
+      const rewards = [100, 100, 100].map((amount) => new anchor.BN(amount * 10 ** 9));
+      const crankFee = new anchor.BN(10 ** 9);
+
+      const tx = await program.rpc.fundSchedule(
+        rewards,
+        crankFee, {
+          accounts: {
+            admin: owner.publicKey,
+            config,
+            schedule,
+            rewardMintTokenAccount,
+            rewardVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId
+          },
+          signers: [owner]
+        }
+      );
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error->", error);
+    }
+  });
+  it("Anyone advances the epoch", async() => {
+    try {
+      const [schedule] = PublicKey.findProgramAddressSync(
+        [Buffer.from("schedule")],
+        program.programId
+      );
+      const configInfo = await program.account.config.fetch(config);
+      const [previousEpoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.addn(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const crankerRewardAccount = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        user2.publicKey
+      );
+
+      const tx = await program.rpc.advanceEpoch({
+        accounts: {
+          cranker: user2.publicKey,
+          config,
+          schedule,
+          previousEpoch,
+          epoch,
+          rewardVault,
+          crankerRewardAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user2]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error->", error);
+    }
+  });
+  */
+  /*
+  it("user 2 tops up the epoch reward", async() => {
+    try {
+      const index = new anchor.BN(1);
+      const amount = 10 * 10 ** 9;
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const funderTokenAccount = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        user2.publicKey
+      );
+
+      const tx = await program.rpc.addEpochReward(
+        index,
+        new anchor.BN(amount), {
+          accounts: {
+            funder: user2.publicKey,
+            config,
+            epoch,
+            funderTokenAccount,
+            rewardVault,
+            tokenProgram: TOKEN_PROGRAM_ID
+          },
+          signers: [user2]
+        }
+      );
+      console.log("tx->", tx);
+      const epochInfo = await program.account.epoch.fetch(epoch);
+      console.log("epochInfo->", epochInfo);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("Add wrapped SOL as an extra reward mint", async() => {
+    try {
+      const [extraRewardVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reward"), NATIVE_MINT.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.addRewardMint({
+        accounts: {
+          config,
+          admin: owner.publicKey,
+          rewardMint: NATIVE_MINT,
+          extraRewardVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 2 co-incentivizes epoch 1 with wrapped SOL", async() => {
+    try {
+      const index = new anchor.BN(1);
+      const amount = LAMPORTS_PER_SOL;
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [extraRewardVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reward"), NATIVE_MINT.toBuffer()],
+        program.programId
+      );
+      const funderTokenAccount = getAssociatedTokenAddressSync(
+        NATIVE_MINT,
+        user2.publicKey
+      );
+
+      const tx = await program.rpc.addExtraEpochReward(
+        index,
+        new anchor.BN(amount), {
+          accounts: {
+            funder: user2.publicKey,
+            config,
+            epoch,
+            rewardMint: NATIVE_MINT,
+            funderTokenAccount,
+            extraRewardVault,
+            tokenProgram: TOKEN_PROGRAM_ID
+          },
+          signers: [user2]
+        }
+      );
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
   it("stake in epoch1", async() => {
     try {
       const index = new anchor.BN(1);
@@ -262,6 +460,12 @@ describe("richie", () => {
             stakeVault,
             epoch,
             stakes,
+            referrerStake: null,
+            referral: null,
+            receiptMint: null,
+            userReceiptAccount: null,
+            associatedTokenProgram: null,
+            boostNftAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId
           },
@@ -293,6 +497,15 @@ describe("richie", () => {
         program.programId
       );
       const lockPeriod = 1;
+      // user 2 was referred by user 1
+      const [referrerStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [referral] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), user2.publicKey.toBuffer()],
+        program.programId
+      );
 
       const tx = await program.rpc.stake(
         index,
@@ -307,6 +520,12 @@ describe("richie", () => {
             stakeVault,
             epoch,
             stakes,
+            referrerStake,
+            referral,
+            receiptMint: null,
+            userReceiptAccount: null,
+            associatedTokenProgram: null,
+            boostNftAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId
           },
@@ -325,109 +544,967 @@ describe("richie", () => {
   });
   */
   /*
-  it("user 1 withdraw token", async() => {
+  it("Require at least 1 token per stake_for deposit", async() => {
+    try {
+      const tx = await program.rpc.updateStakeForMinimum(new anchor.BN(10 ** 9), {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("owner stake for user 2 in epoch1", async() => {
     try {
       const index = new anchor.BN(1);
-      
+      const amount = 10 * 10 ** 9;
       const [userStake] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        [Buffer.from("user"), user2.publicKey.toBuffer()],
         program.programId
       );
+      const fromTokenAccount = getAssociatedTokenAddressSync(
+        stakeTokenMint,
+        owner.publicKey
+      );
       const [epoch] = PublicKey.findProgramAddressSync(
         [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
-      const userStakeData = await program.account.userStake.fetch(userStake);
-      console.log("userStakeData->", userStakeData);
-      const toTokenAccount = getAssociatedTokenAddressSync(
-        stakeTokenMint,
-        user1.publicKey
-      );
+      const lockPeriod = 4;
 
-      const tx = await program.rpc.withdraw(
-        new anchor.BN(1), {
+      const tx = await program.rpc.stakeFor(
+        index,
+        new anchor.BN(amount),
+        lockPeriod, {
           accounts: {
-            user: user1.publicKey,
+            funder: owner.publicKey,
+            beneficiary: user2.publicKey,
             config,
-            epoch,
-            userStake,
             stakeTokenMint,
+            userStake,
+            fromTokenAccount,
             stakeVault,
-            toTokenAccount,
-            tokenProgram: TOKEN_PROGRAM_ID
+            epoch,
+            stakes,
+            boostNftAccount: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId
           },
-          signers: [user1]
+          signers: [owner]
         }
       );
       console.log("tx->", tx);
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+      console.log("userStakeInfo->", userStakeInfo);
     } catch (error) {
       console.log("error:", error);
     }
   });
   */
   /*
-  it("Manage rewards", async() => {
+  it("user 1 stake with receipt in epoch1", async() => {
     try {
-      const users = await program.account.userStake.all();
-      for(let i = 0; i<users.length; i++) {
-        const user = users[i];
-        const index = new anchor.BN(1);
-        const userStake = user.publicKey
-     
-        const [epoch] = PublicKey.findProgramAddressSync(
-          [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
-          program.programId
-        );
+      const index = new anchor.BN(1);
+      const amount = 5 * 10 ** 9;
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+      const [receiptMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("receipt"), userStake.toBuffer(), userStakeInfo.nextEntryId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const userReceiptAccount = getAssociatedTokenAddressSync(
+        receiptMint,
+        user1.publicKey
+      );
+      const fromTokenAccount = getAssociatedTokenAddressSync(
+        stakeTokenMint,
+        user1.publicKey
+      );
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const lockPeriod = 4;
 
-        const tx = await program.rpc.manageStakerReward(
-          index,
-          {
-            accounts: {
-              admin: owner.publicKey,
-              config,
-              epoch,
-              user: user.account.owner,
-              userStake,
-              stakes,
-              tokenProgram: TOKEN_PROGRAM_ID,
-              systemProgram: SystemProgram.programId
-            },
-            signers: [owner]
-          }
-        );
-        console.log("tx->", tx);
-      }
+      const tx = await program.rpc.stake(
+        index,
+        new anchor.BN(amount),
+        lockPeriod, {
+          accounts: {
+            user: user1.publicKey,
+            config,
+            stakeTokenMint,
+            userStake,
+            fromTokenAccount,
+            stakeVault,
+            epoch,
+            stakes,
+            referrerStake: null,
+            referral: null,
+            receiptMint,
+            userReceiptAccount,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            boostNftAccount: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
     } catch (error) {
       console.log("error:", error);
     }
   });
   */
   /*
-  it("User 1 Cliam the reward", async() => {
+  it("user 2 stake into the liquid pool", async() => {
     try {
       const index = new anchor.BN(1);
-
-      const [userStake] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), user1.publicKey.toBuffer()],
+      const amount = 10 * 10 ** 9;
+      const [liquidStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), config.toBuffer()],
         program.programId
       );
-      const userRewardAccount = getAssociatedTokenAddressSync(
+      const [liquidMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("liquid")],
+        program.programId
+      );
+      const userLiquidAccount = getAssociatedTokenAddressSync(
+        liquidMint,
+        user2.publicKey
+      );
+      const fromTokenAccount = getAssociatedTokenAddressSync(
+        stakeTokenMint,
+        user2.publicKey
+      );
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.rpc.stakeLiquid(
+        index,
+        new anchor.BN(amount), {
+          accounts: {
+            user: user2.publicKey,
+            config,
+            liquidStake,
+            liquidMint,
+            userLiquidAccount,
+            fromTokenAccount,
+            stakeVault,
+            epoch,
+            stakes,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId
+          },
+          signers: [user2]
+        }
+      );
+      console.log("tx->", tx);
+      const liquidStakeInfo = await program.account.userStake.fetch(liquidStake);
+      console.log("liquidStakeInfo->", liquidStakeInfo);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 2 redeems liquid shares, unbonding when the pool has a cooldown", async() => {
+    try {
+      const [liquidStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), config.toBuffer()],
+        program.programId
+      );
+      const [liquidMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("liquid")],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.rpc.redeemLiquid(new anchor.BN(5 * 10 ** 9), {
+        accounts: {
+          user: user2.publicKey,
+          config,
+          epoch,
+          liquidStake,
+          liquidMint,
+          userLiquidAccount: getAssociatedTokenAddressSync(liquidMint, user2.publicKey),
+          stakeVault,
+          toTokenAccount: getAssociatedTokenAddressSync(stakeTokenMint, user2.publicKey),
+          userStake,
+          stakes,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user2]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 set user 2 as operator", async() => {
+    try {
+      const claimPermission = 1;
+      const extendLockPermission = 4;
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.setOperator(
+        user2.publicKey,
+        claimPermission | extendLockPermission, {
+          accounts: {
+            user: user1.publicKey,
+            config,
+            userStake
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 extend lock", async() => {
+    try {
+      const entryId = new anchor.BN(1);
+      const lockPeriod = 8;
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.extendLock(
+        entryId,
+        lockPeriod, {
+          accounts: {
+            authority: user1.publicKey,
+            config,
+            userStake
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
+      const entry = await program.methods.getStakeEntry(entryId).accounts({ userStake }).view();
+      console.log("entry->", entry);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 merge expired entries", async() => {
+    try {
+      const entryIds = [new anchor.BN(0), new anchor.BN(1)];
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.mergeEntries(
+        entryIds, {
+          accounts: {
+            user: user1.publicKey,
+            config,
+            userStake
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+      console.log("userStakeInfo->", userStakeInfo);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 transfer position to user 2", async() => {
+    try {
+      const entryIds = [new anchor.BN(1)];
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [recipientStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const configData = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configData.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.rpc.transferPosition(
+        entryIds, {
+          accounts: {
+            user: user1.publicKey,
+            config,
+            epoch,
+            userStake,
+            recipient: user2.publicKey,
+            recipientStake,
+            stakes,
+            systemProgram: SystemProgram.programId
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
+      const recipientStakeInfo = await program.account.userStake.fetch(recipientStake);
+      console.log("recipientStakeInfo->", recipientStakeInfo);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 withdraw token", async() => {
+    try {
+      const index = new anchor.BN(1);
+      
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const userStakeData = await program.account.userStake.fetch(userStake);
+      console.log("userStakeData->", userStakeData);
+      const toTokenAccount = getAssociatedTokenAddressSync(
+        stakeTokenMint,
+        user1.publicKey
+      );
+
+      const entryId = new anchor.BN(1);
+      const tx = await program.rpc.withdraw(
+        entryId, {
+          accounts: {
+            user: user1.publicKey,
+            config,
+            epoch,
+            userStake,
+            stakeTokenMint,
+            stakeVault,
+            toTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID
+          },
+          signers: [user1]
+        }
+      );
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 voting power", async() => {
+    try {
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const votingPower = await program.methods.getVotingPower()
+        .accounts({ config, userStake })
+        .view();
+      console.log("votingPower->", votingPower);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("Use the pool as voter weight add-in with a 3 day vote lock", async() => {
+    try {
+      const realm = new PublicKey(process.env.REALM);
+      const governingTokenMint = new PublicKey(process.env.GOVERNING_TOKEN_MINT);
+      const tx = await program.rpc.updateGovernance(realm, governingTokenMint, new anchor.BN(3 * 24 * 60 * 60), {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 1 update voter weight record", async() => {
+    try {
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const configInfo = await program.account.config.fetch(config);
+      const [voterWeightRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("voter-weight-record"), configInfo.realm.toBuffer(), configInfo.governingTokenMint.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [maxVoterWeightRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("max-voter-weight-record"), configInfo.realm.toBuffer(), configInfo.governingTokenMint.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.updateVoterWeightRecord({
+        accounts: {
+          payer: user1.publicKey,
+          config,
+          userStake,
+          voterWeightRecord,
+          maxVoterWeightRecord,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+      console.log("voterWeightRecord->", await program.account.voterWeightRecord.fetch(voterWeightRecord));
+      console.log("maxVoterWeightRecord->", await program.account.maxVoterWeightRecord.fetch(maxVoterWeightRecord));
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 1 request unstake", async() => {
+    try {
+      const entryId = new anchor.BN(1);
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.methods.requestUnstake(entryId)
+        .accounts({
+          authority: user1.publicKey,
+          config,
+          epoch,
+          userStake,
+          receiptAccount: null
+        })
+        .signers([user1])
+        .rpc();
+      console.log("tx->", tx);
+      const entry = await program.methods.getStakeEntry(entryId).accounts({ userStake }).view();
+      console.log("entry->", entry);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("user 2 withdraw and claim", async() => {
+    try {
+      const entryId = new anchor.BN(0);
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const toTokenAccount = getAssociatedTokenAddressSync(
+        stakeTokenMint,
+        user2.publicKey
+      );
+      const destination = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        user2.publicKey
+      );
+
+      const tx = await program.rpc.withdrawAndClaim(
+        entryId,
+        1, { // primary reward only
+          accounts: {
+            user: user2.publicKey,
+            config,
+            epoch,
+            userStake,
+            stakeTokenMint,
+            stakeVault,
+            toTokenAccount,
+            rewardVault,
+            rewardMint: rewardTokenMint,
+            destination,
+            tokenProgram: TOKEN_PROGRAM_ID
+          },
+          signers: [user2]
+        }
+      );
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  // Root, total, next total curve and proofs come from the program's merkle helper run against account snapshots
+  it("Publish the epoch 1 merkle root", async() => {
+    try {
+      const index = new anchor.BN(1);
+      const merkleRoot = Array.from(Buffer.alloc(32, 1));
+      const total = new anchor.BN(100 * 10 ** 9);
+      const nextTotalCurve = new anchor.BN(process.env.NEXT_TOTAL_CURVE); // merkle::rolled_total_curve
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.rpc.publishMerkleRoot(index, merkleRoot, total, nextTotalCurve, {
+        accounts: {
+          admin: owner.publicKey,
+          config,
+          epoch
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("User 1 claims the epoch 1 merkle reward", async() => {
+    try {
+      const index = new anchor.BN(1);
+      const amount = new anchor.BN(60 * 10 ** 9);
+      const proof = [Array.from(Buffer.alloc(32, 2))];
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [merkleClaim] = PublicKey.findProgramAddressSync(
+        [Buffer.from("merkle_claim"), index.toArrayLike(Buffer, "le", 8), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.claimMerkleReward(index, amount, proof, {
+        accounts: {
+          payer: user1.publicKey,
+          config,
+          epoch,
+          userStake,
+          merkleClaim,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Manage rewards", async() => {
+    try {
+      const users = await program.account.userStake.all();
+      for(let i = 0; i<users.length; i++) {
+        const user = users[i];
+        const index = new anchor.BN(1);
+        const userStake = user.publicKey
+        const hasReferrer = !user.account.referrer.equals(PublicKey.default);
+        const [referrerStake] = PublicKey.findProgramAddressSync(
+          [Buffer.from("user"), user.account.referrer.toBuffer()],
+          program.programId
+        );
+        const [referral] = PublicKey.findProgramAddressSync(
+          [Buffer.from("referral"), user.account.owner.toBuffer()],
+          program.programId
+        );
+     
+        const [epoch] = PublicKey.findProgramAddressSync(
+          [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+
+        const tx = await program.rpc.manageStakerReward(
+          index,
+          {
+            accounts: {
+              admin: owner.publicKey,
+              config,
+              epoch,
+              user: user.account.owner,
+              userStake,
+              stakes,
+              referrerStake: hasReferrer ? referrerStake : null,
+              referral: hasReferrer ? referral : null,
+              boostNftAccount: user.account.boostNftMint.equals(PublicKey.default) ? null : user.account.boostNftAccount,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId
+            },
+            signers: [owner]
+          }
+        );
+        console.log("tx->", tx);
+      }
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  */
+  /*
+  it("User 1 Cliam the reward", async() => {
+    try {
+      const index = new anchor.BN(1);
+
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const userRewardAccount = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        user1.publicKey
+      );
+
+      const tx = await program.rpc.claim(1, { // primary reward only
+        accounts: {
+          authority: user1.publicKey,
+          user: user1.publicKey,
+          config,
+          userStake,
+          rewardVault,
+          rewardMint: rewardTokenMint,
+          destination: userRewardAccount,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+  it("User 2 Cliam the reward", async() => {
+    try {
+      const index = new anchor.BN(1);
+
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const userRewardAccount = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        user2.publicKey
+      );
+      const [partnerRewardVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reward"), NATIVE_MINT.toBuffer()],
+        program.programId
+      );
+      const userPartnerAccount = getAssociatedTokenAddressSync(
+        NATIVE_MINT,
+        user2.publicKey
+      );
+
+      // Primary reward plus extra slot 0; each extra reward takes its vault and a destination
+      const tx = await program.rpc.claim(0b11, {
+        accounts: {
+          authority: user2.publicKey,
+          user: user2.publicKey,
+          config,
+          userStake,
+          rewardVault,
+          rewardMint: rewardTokenMint,
+          destination: userRewardAccount,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        remainingAccounts: [
+          { pubkey: partnerRewardVault, isWritable: true, isSigner: false },
+          { pubkey: userPartnerAccount, isWritable: true, isSigner: false }
+        ],
+        signers: [user2]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+ 
+  it("Expire unclaimed rewards after 4 epochs", async() => {
+    try {
+      // PublicKey.default returns swept rewards to the pool
+      const tx = await program.rpc.updateClaimExpiry(new anchor.BN(4), PublicKey.default, {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Anyone sweeps user 1 expired rewards", async() => {
+    try {
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.sweepExpiredRewards({
+        accounts: {
+          cranker: user2.publicKey,
+          config,
+          userStake,
+          rewardVault,
+          treasury: null,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [user2]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Anyone releases the unclaimed epoch 1 merkle reserve", async() => {
+    try {
+      const index = new anchor.BN(1);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const tx = await program.rpc.releaseUnclaimedMerkle(index, {
+        accounts: {
+          cranker: user2.publicKey,
+          config,
+          epoch,
+          rewardVault,
+          treasury: null,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [user2]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Take a 5% protocol fee for the treasury", async() => {
+    try {
+      const treasury = getAssociatedTokenAddressSync(
         rewardTokenMint,
+        owner.publicKey
+      );
+      const tx = await program.rpc.updateProtocolFee(500, owner.publicKey, treasury, {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Referrers earn 5% of their referees' rewards", async() => {
+    try {
+      const tx = await program.rpc.updateReferralBps(500, {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Reward 4 and 12 epoch streaks with +10% and +25%", async() => {
+    try {
+      const tiers = [
+        { minStreak: new anchor.BN(4), bonus: new anchor.BN(10) },
+        { minStreak: new anchor.BN(12), bonus: new anchor.BN(25) }
+      ];
+      const tx = await program.rpc.updateLoyaltyTiers(tiers, {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Collection holders get +20% on locked stake", async() => {
+    try {
+      const collection = new PublicKey(process.env.NFT_COLLECTION); // verified collection of the boosting NFTs
+      const tx = await program.rpc.updateNftBoost(new anchor.BN(20), [collection], {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 1 registers a collection NFT for the boost", async() => {
+    try {
+      const nftMint = new PublicKey(process.env.BOOST_NFT_MINT); // NFT of the collection held by user 1
+      const metadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [nftMetadata] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), metadataProgram.toBuffer(), nftMint.toBuffer()],
+        metadataProgram
+      );
+      const nftTokenAccount = getAssociatedTokenAddressSync(
+        nftMint,
         user1.publicKey
       );
 
-      const tx = await program.rpc.claim({
+      const [nftBoostLock] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_boost_lock"), nftMint.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.registerNftBoost({
         accounts: {
           user: user1.publicKey,
           config,
           userStake,
-          rewardVault,
-          rewardMint: rewardTokenMint,
-          userRewardAccount,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          nftMint,
+          nftTokenAccount,
+          nftMetadata,
+          nftBoostLock,
+          previousUserStake: null,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Initialize the governance token boost vault", async() => {
+    try {
+      const boostTokenMint = new PublicKey(process.env.BOOST_TOKEN_MINT);
+      const [boostVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("boost_vault")],
+        program.programId
+      );
+      const tx = await program.rpc.initializeBoostVault({
+        accounts: {
+          config,
+          admin: owner.publicKey,
+          boostTokenMint,
+          boostVault,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId
         },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+
+      // Up to +150% once the boost stake reaches 20% of the main stake
+      const tx2 = await program.rpc.updateDualBoost(new anchor.BN(150), new anchor.BN(2000), {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx2->", tx2);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 1 stakes governance tokens to boost", async() => {
+    try {
+      const boostTokenMint = new PublicKey(process.env.BOOST_TOKEN_MINT);
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [boostVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("boost_vault")],
+        program.programId
+      );
+      const userTokenAccount = getAssociatedTokenAddressSync(
+        boostTokenMint,
+        user1.publicKey
+      );
+
+      const tx = await program.rpc.stakeBoost(new anchor.BN(5 * 10 ** 9), {
+        accounts: {
+          user: user1.publicKey,
+          config,
+          epoch,
+          userStake,
+          userTokenAccount,
+          boostVault,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
         signers: [user1]
       });
       console.log("tx->", tx);
@@ -435,39 +1512,287 @@ describe("richie", () => {
       console.log("error:", error);
     }
   });
-  it("User 2 Cliam the reward", async() => {
+
+  it("Switch an empty pool to staking collection NFTs", async() => {
     try {
-      const index = new anchor.BN(1);
+      const collection = new PublicKey(process.env.POOL_NFT_COLLECTION); // verified collection staked in the pool
+      // Each NFT counts as 100 tokens; early exits cost 5 reward tokens
+      const tx = await program.rpc.updateNftPool(collection, new anchor.BN(100 * 10 ** 9), new anchor.BN(5 * 10 ** 9), {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
 
+  it("user 1 stakes a collection NFT", async() => {
+    try {
+      const nftMint = new PublicKey(process.env.POOL_NFT_MINT); // NFT of the pool collection held by user 1
+      const metadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
       const [userStake] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), user2.publicKey.toBuffer()],
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
         program.programId
       );
-      const userRewardAccount = getAssociatedTokenAddressSync(
-        rewardTokenMint,
-        user2.publicKey
+      const [nftMetadata] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), metadataProgram.toBuffer(), nftMint.toBuffer()],
+        metadataProgram
+      );
+      const [nftEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_escrow"), nftMint.toBuffer()],
+        program.programId
+      );
+      const fromNftAccount = getAssociatedTokenAddressSync(
+        nftMint,
+        user1.publicKey
       );
 
-      const tx = await program.rpc.claim({
+      const tx = await program.rpc.stakeNft(configInfo.index, 4, {
         accounts: {
-          user: user2.publicKey,
+          user: user1.publicKey,
+          config,
+          nftMint,
+          nftMetadata,
+          fromNftAccount,
+          nftEscrow,
+          userStake,
+          epoch,
+          stakes,
+          boostNftAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 1 withdraws the staked NFT early, paying the penalty in reward tokens", async() => {
+    try {
+      const nftMint = new PublicKey(process.env.POOL_NFT_MINT);
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [nftEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_escrow"), nftMint.toBuffer()],
+        program.programId
+      );
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+      const entry = userStakeInfo.stakeEntries.find((e) => e.nftMint.equals(nftMint));
+
+      const tx = await program.rpc.withdrawNft(entry.id, {
+        accounts: {
+          user: user1.publicKey,
           config,
+          epoch,
           userStake,
+          nftMint,
+          nftEscrow,
+          toNftAccount: getAssociatedTokenAddressSync(nftMint, user1.publicKey),
           rewardVault,
-          rewardMint: rewardTokenMint,
-          userRewardAccount,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          penaltyTokenAccount: getAssociatedTokenAddressSync(rewardTokenMint, user1.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  // Native pools: stake_token_mint / reward_token_mint set to NATIVE_MINT at initialization
+  it("user 1 stakes SOL directly", async() => {
+    try {
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.rpc.stakeSol(configInfo.index, new anchor.BN(2 * LAMPORTS_PER_SOL), 2, {
+        accounts: {
+          user: user1.publicKey,
+          config,
+          userStake,
+          stakeVault,
+          epoch,
+          stakes,
+          boostNftAccount: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId
         },
-        signers: [user2]
+        signers: [user1]
       });
       console.log("tx->", tx);
     } catch (error) {
       console.log("error:", error);
     }
   });
- 
+
+  it("user 1 withdraws staked SOL and claims SOL rewards", async() => {
+    try {
+      const configInfo = await program.account.config.fetch(config);
+      const [epoch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("epoch"), configInfo.index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [unwrapAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("unwrap"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+
+      const tx = await program.rpc.withdrawSol(userStakeInfo.stakeEntries[0].id, {
+        accounts: {
+          user: user1.publicKey,
+          config,
+          epoch,
+          userStake,
+          nativeMint: NATIVE_MINT,
+          stakeVault,
+          unwrapAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+
+      const tx2 = await program.rpc.claimSol({
+        accounts: {
+          user: user1.publicKey,
+          config,
+          userStake,
+          nativeMint: NATIVE_MINT,
+          rewardVault,
+          unwrapAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx2->", tx2);
+      console.log("user1 SOL balance:", await program.provider.connection.getBalance(user1.publicKey));
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("user 1 completes a SOL unbonding", async() => {
+    try {
+      const [userStake] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [unwrapAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("unwrap"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const userStakeInfo = await program.account.userStake.fetch(userStake);
+      const entry = userStakeInfo.stakeEntries.find((e) => !e.unbondReleaseTime.isZero());
+
+      const tx = await program.rpc.completeUnstakeSol(entry.id, {
+        accounts: {
+          user: user1.publicKey,
+          config,
+          userStake,
+          nativeMint: NATIVE_MINT,
+          stakeVault,
+          unwrapAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        },
+        signers: [user1]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Treasurer withdraws the SOL early-exit penalties", async() => {
+    try {
+      const tx = await program.rpc.withdrawNativePenalties({
+        accounts: {
+          treasurer: owner.publicKey,
+          config,
+          stakeVault,
+          destination: getAssociatedTokenAddressSync(NATIVE_MINT, owner.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Treasurer withdraws the accrued fees", async() => {
+    try {
+      const treasury = getAssociatedTokenAddressSync(
+        rewardTokenMint,
+        owner.publicKey
+      );
+      const tx = await program.rpc.withdrawProtocolFees({
+        accounts: {
+          treasurer: owner.publicKey,
+          config,
+          rewardVault,
+          treasury,
+          tokenProgram: TOKEN_PROGRAM_ID
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
+  it("Update emission policy to halving", async() => {
+    try {
+      const policy = { halving: { initial: new anchor.BN(100 * 10 ** 9), interval: new anchor.BN(26) } };
+      const tx = await program.rpc.updateEmissionPolicy(policy, {
+        accounts: {
+          config,
+          admin: owner.publicKey
+        },
+        signers: [owner]
+      });
+      console.log("tx->", tx);
+    } catch (error) {
+      console.log("error:", error);
+    }
+  });
+
   it("Update duration", async() => {
     try {
       const duration = 14 * 24 * 60 * 60; // 14 days